@@ -0,0 +1,77 @@
+//! Reads a register from an I2C slave using a 10-bit address
+
+#![no_std]
+#![no_main]
+
+extern crate cortex_m;
+#[macro_use]
+extern crate cortex_m_rt as rt;
+extern crate cortex_m_semihosting as sh;
+extern crate panic_semihosting;
+extern crate stm32l4xx_hal as hal;
+
+use crate::hal::prelude::*;
+
+use crate::hal::i2c;
+use crate::hal::i2c::{Address, I2c};
+use crate::rt::entry;
+use crate::rt::ExceptionFrame;
+
+use crate::sh::hio;
+use core::fmt::Write;
+
+#[entry]
+fn main() -> ! {
+    let mut hstdout = hio::hstdout().unwrap();
+
+    let dp = hal::stm32::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let mut rcc = dp.RCC.constrain();
+    let mut pwr = dp.PWR.constrain(&mut rcc.apb1r1);
+
+    let clocks = rcc.cfgr.freeze(&mut flash.acr, &mut pwr);
+
+    let mut gpioa = dp.GPIOA.split(&mut rcc.ahb2);
+
+    let mut scl =
+        gpioa
+            .pa9
+            .into_alternate_open_drain(&mut gpioa.moder, &mut gpioa.otyper, &mut gpioa.afrh);
+    scl.internal_pull_up(&mut gpioa.pupdr, true);
+
+    let mut sda =
+        gpioa
+            .pa10
+            .into_alternate_open_drain(&mut gpioa.moder, &mut gpioa.otyper, &mut gpioa.afrh);
+    sda.internal_pull_up(&mut gpioa.pupdr, true);
+
+    let mut i2c = I2c::i2c1(
+        dp.I2C1,
+        (scl, sda),
+        i2c::Config::new(100.kHz(), clocks),
+        &mut rcc.apb1r1,
+    );
+
+    const SLAVE_ADDR: Address = Address::TenBit(0x2A5);
+    let mut buffer = [0u8; 2];
+
+    // Select register 0x00 and read it back in two separate transactions
+    i2c.write_addr(SLAVE_ADDR, &[0x00]).unwrap();
+    i2c.read_addr(SLAVE_ADDR, &mut buffer).unwrap();
+    writeln!(hstdout, "Register 0x00: {:?}", buffer).ok();
+
+    // Do the same using a repeated start
+    i2c.write_read_addr(SLAVE_ADDR, &[0x01], &mut buffer)
+        .unwrap();
+    writeln!(hstdout, "Register 0x01: {:?}", buffer).ok();
+
+    loop {
+        continue;
+    }
+}
+
+#[exception]
+unsafe fn HardFault(ef: &ExceptionFrame) -> ! {
+    panic!("{:#?}", ef);
+}
@@ -13,32 +13,9 @@ use stm32l4xx_hal::prelude::*;
 use stm32l4xx_hal::rcc::{
     ClockSecuritySystem, CrystalBypass, MsiFreq, PllConfig, PllDivider, PllSource,
 };
-use stm32l4xx_hal::stm32::{Peripherals, CRS, PWR, RCC};
+use stm32l4xx_hal::stm32::Peripherals;
 use usb_device::prelude::*;
 
-/// Enable CRS (Clock Recovery System)
-fn enable_crs() {
-    let rcc = unsafe { &(*RCC::ptr()) };
-    rcc.apb1enr1.modify(|_, w| w.crsen().set_bit());
-    let crs = unsafe { &(*CRS::ptr()) };
-    // Initialize clock recovery
-    // Set autotrim enabled.
-    crs.cr.modify(|_, w| w.autotrimen().set_bit());
-    // Enable CR
-    crs.cr.modify(|_, w| w.cen().set_bit());
-}
-
-/// Enables VddUSB power supply
-fn enable_usb_pwr() {
-    // Enable PWR peripheral
-    let rcc = unsafe { &(*RCC::ptr()) };
-    rcc.apb1enr1.modify(|_, w| w.pwren().set_bit());
-
-    // Enable VddUSB
-    let pwr = unsafe { &*PWR::ptr() };
-    pwr.cr2.modify(|_, w| w.usv().set_bit());
-}
-
 static mut EP_MEMORY: [u32; 1024] = [0; 1024];
 
 #[entry]
@@ -81,10 +58,10 @@ unsafe fn main() -> ! {
         }
     };
 
-    // Enable clock recovery system.
-    enable_crs();
+    // The 48 MHz MSI is used as the USB clock. The clock recovery system (see the `crs` module)
+    // only trims the HSI48, which is not available on all stm32l4x5 and stm32l4x6 devices.
     // Enable USB power (and disable VddUSB power isolation).
-    enable_usb_pwr();
+    pwr.enable_vddusb();
 
     let mut gpioa = dp.GPIOA.split(&mut rcc.ahb2);
 
@@ -6,33 +6,12 @@
 extern crate panic_semihosting;
 
 use cortex_m_rt::entry;
+use stm32l4xx_hal::crs::{self, CrsExt};
 use stm32l4xx_hal::usb::{Peripheral, UsbBus};
 use stm32l4xx_hal::{prelude::*, stm32};
 use usb_device::prelude::*;
 use usbd_serial::{SerialPort, USB_CLASS_CDC};
 
-fn enable_crs() {
-    let rcc = unsafe { &(*stm32::RCC::ptr()) };
-    rcc.apb1enr1.modify(|_, w| w.crsen().set_bit());
-    let crs = unsafe { &(*stm32::CRS::ptr()) };
-    // Initialize clock recovery
-    // Set autotrim enabled.
-    crs.cr.modify(|_, w| w.autotrimen().set_bit());
-    // Enable CR
-    crs.cr.modify(|_, w| w.cen().set_bit());
-}
-
-/// Enables VddUSB power supply
-fn enable_usb_pwr() {
-    // Enable PWR peripheral
-    let rcc = unsafe { &(*stm32::RCC::ptr()) };
-    rcc.apb1enr1.modify(|_, w| w.pwren().set_bit());
-
-    // Enable VddUSB
-    let pwr = unsafe { &*stm32::PWR::ptr() };
-    pwr.cr2.modify(|_, w| w.usv().set_bit());
-}
-
 #[entry]
 fn main() -> ! {
     let dp = stm32::Peripherals::take().unwrap();
@@ -41,16 +20,19 @@ fn main() -> ! {
     let mut rcc = dp.RCC.constrain();
     let mut pwr = dp.PWR.constrain(&mut rcc.apb1r1);
 
-    let _clocks = rcc
+    let clocks = rcc
         .cfgr
         .hsi48(true)
         .sysclk(80.MHz())
         .freeze(&mut flash.acr, &mut pwr);
 
-    enable_crs();
+    // Trim the HSI48 against the USB SOF packets
+    let _crs = dp
+        .CRS
+        .enable(crs::Config::default(), clocks, &mut rcc.apb1r1);
 
     // disable Vddusb power isolation
-    enable_usb_pwr();
+    pwr.enable_vddusb();
 
     // Configure the on-board LED (LD3, green)
     let mut gpiob = dp.GPIOB.split(&mut rcc.ahb2);
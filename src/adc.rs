@@ -38,6 +38,8 @@ pub struct ADC {
     resolution: Resolution,
     sample_time: SampleTime,
     calibrated_vdda: u32,
+    pending_channel: Option<u8>,
+    discarded: bool,
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -136,11 +138,14 @@ impl ADC {
         // Reset peripheral
         ADC1::reset(ahb);
 
-        // Select system clock as ADC clock source
-        ccipr.ccipr().modify(|_, w| {
-            // This is sound, as `0b11` is a valid value for this field.
-            unsafe {
-                w.adcsel().bits(0b11);
+        // Select system clock as ADC clock source, unless the PLLSAI1 R output was already
+        // selected by `rcc::CFGR::freeze`
+        ccipr.ccipr().modify(|r, w| {
+            if r.adcsel().bits() == 0b00 {
+                // This is sound, as `0b11` is a valid value for this field.
+                unsafe {
+                    w.adcsel().bits(0b11);
+                }
             }
 
             w
@@ -176,6 +181,8 @@ impl ADC {
             resolution: Resolution::default(),
             sample_time: SampleTime::default(),
             calibrated_vdda: VDDA_CALIB_MV,
+            pending_channel: None,
+            discarded: false,
         };
 
         // Temporarily enable Vref
@@ -387,6 +394,9 @@ impl ADC {
         self.enable();
         self.clear_end_flags();
         self.adc.cr.modify(|_, w| w.adstart().set_bit());
+
+        // Any conversion started by `read_nb` is superseded by this one
+        self.pending_channel = None;
     }
 
     pub fn is_converting(&self) -> bool {
@@ -433,6 +443,99 @@ impl ADC {
     pub fn disable(&mut self) {
         self.adc.cr.modify(|_, w| w.addis().set_bit());
     }
+
+    /// Non-blocking conversion of a single channel
+    ///
+    /// The first call configures the channel and starts a conversion, following calls return
+    /// `WouldBlock` until the conversion has completed, at which point the sample is returned
+    /// and the ADC is disabled. Like [`OneShot::read`], the first conversion is discarded as per
+    /// errata sheet and a second one is started in its place.
+    ///
+    /// If a conversion of a different channel is pending, it is discarded and a conversion of
+    /// `channel` is started instead. Starting any other conversion also discards the pending
+    /// one.
+    pub fn read_nb<C>(&mut self, channel: &mut C) -> nb::Result<u16, Infallible>
+    where
+        C: Channel,
+    {
+        if self.pending_channel != Some(C::channel()) {
+            if self.is_converting() {
+                self.adc.cr.modify(|_, w| w.adstp().set_bit());
+                while self.adc.cr.read().adstp().bit_is_set() {}
+            }
+
+            self.configure_sequence(channel, Sequence::One, self.sample_time);
+            self.start_conversion();
+            self.pending_channel = Some(C::channel());
+            self.discarded = false;
+
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if !self.has_completed_sequence() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if !self.discarded {
+            // Discard the first conversion, see `OneShot::read`
+            let _ = self.get_data();
+
+            self.start_conversion();
+            self.pending_channel = Some(C::channel());
+            self.discarded = true;
+
+            return Err(nb::Error::WouldBlock);
+        }
+
+        let val = self.get_data();
+        self.clear_end_flags();
+        self.pending_channel = None;
+
+        self.disable();
+
+        Ok(val)
+    }
+
+    /// Starts converting a single channel continuously
+    ///
+    /// The ADC free-runs and the data register is overwritten with every new sample, so
+    /// [`ADC::latest`] returns the most recent conversion without waiting. Use
+    /// [`ADC::stop_continuous`] before doing any other conversions.
+    pub fn start_continuous<C>(&mut self, channel: &mut C)
+    where
+        C: Channel,
+    {
+        self.reset_sequence();
+        self.configure_sequence(channel, Sequence::One, self.sample_time);
+
+        // Keep converting on overrun, overwriting the data register with the latest sample
+        self.adc
+            .cfgr
+            .modify(|_, w| w.cont().set_bit().ovrmod().set_bit());
+
+        self.start_conversion();
+    }
+
+    /// Returns the most recent sample of a continuous conversion
+    ///
+    /// Until the first conversion has completed, this returns the previous contents of the data
+    /// register.
+    pub fn latest(&self) -> u16 {
+        self.get_data()
+    }
+
+    /// Stops a continuous conversion started by [`ADC::start_continuous`]
+    pub fn stop_continuous(&mut self) {
+        if self.is_converting() {
+            self.adc.cr.modify(|_, w| w.adstp().set_bit());
+            while self.adc.cr.read().adstp().bit_is_set() {}
+        }
+
+        self.adc
+            .cfgr
+            .modify(|_, w| w.cont().clear_bit().ovrmod().clear_bit());
+        self.clear_end_flags();
+    }
 }
 
 impl<C> OneShot<ADC, u16, C> for ADC
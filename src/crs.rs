@@ -0,0 +1,194 @@
+//! Clock recovery system (CRS)
+//!
+//! The CRS trims the 48 MHz HSI against a synchronization signal, usually the 1 kHz USB
+//! start-of-frame (SOF), which allows crystal-less USB operation.
+//!
+//! Usage example:
+//! ```ignore
+//! let clocks = rcc.cfgr.hsi48(true).freeze(&mut flash.acr, &mut pwr);
+//!
+//! // Trim the HSI48 against the USB SOF packets
+//! let crs = dp.CRS.enable(crs::Config::default(), clocks, &mut rcc.apb1r1);
+//! ```
+
+use crate::rcc::{Clocks, Enable, APB1R1};
+use crate::stm32::CRS;
+
+/// Synchronization signal source
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SyncSource {
+    /// CRS_SYNC pin
+    Gpio = 0b00,
+    /// 32.768 kHz LSE
+    Lse = 0b01,
+    /// USB start-of-frame, 1 kHz
+    UsbSof = 0b10,
+}
+
+/// Synchronization signal polarity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SyncPolarity {
+    /// Synchronize on the rising edge
+    Rising,
+    /// Synchronize on the falling edge
+    Falling,
+}
+
+/// Divider applied to the synchronization signal
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SyncDivider {
+    /// Not divided
+    Div1 = 0b000,
+    /// Divided by 2
+    Div2 = 0b001,
+    /// Divided by 4
+    Div4 = 0b010,
+    /// Divided by 8
+    Div8 = 0b011,
+    /// Divided by 16
+    Div16 = 0b100,
+    /// Divided by 32
+    Div32 = 0b101,
+    /// Divided by 64
+    Div64 = 0b110,
+    /// Divided by 128
+    Div128 = 0b111,
+}
+
+/// CRS configuration
+///
+/// The default synchronizes on the rising edge of the USB SOF with the reload value and
+/// frequency error limit recommended by the reference manual.
+pub struct Config {
+    source: SyncSource,
+    polarity: SyncPolarity,
+    divider: SyncDivider,
+    reload: u16,
+    error_limit: u8,
+}
+
+impl Config {
+    /// Set the synchronization signal source
+    pub fn source(mut self, source: SyncSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Set the synchronization signal polarity
+    pub fn polarity(mut self, polarity: SyncPolarity) -> Self {
+        self.polarity = polarity;
+        self
+    }
+
+    /// Set the synchronization signal divider
+    pub fn divider(mut self, divider: SyncDivider) -> Self {
+        self.divider = divider;
+        self
+    }
+
+    /// Set the counter reload value, i.e. `(48 MHz / f_sync) - 1`
+    pub fn reload(mut self, reload: u16) -> Self {
+        self.reload = reload;
+        self
+    }
+
+    /// Set the frequency error limit, in counter steps
+    pub fn error_limit(mut self, error_limit: u8) -> Self {
+        self.error_limit = error_limit;
+        self
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            source: SyncSource::UsbSof,
+            polarity: SyncPolarity::Rising,
+            divider: SyncDivider::Div1,
+            reload: 47_999,
+            error_limit: 34,
+        }
+    }
+}
+
+/// Extension trait to activate the CRS
+pub trait CrsExt {
+    /// Configures the CRS and enables automatic trimming of the HSI48
+    fn enable(self, config: Config, clocks: Clocks, apb1r1: &mut APB1R1) -> Crs;
+}
+
+impl CrsExt for CRS {
+    fn enable(self, config: Config, clocks: Clocks, apb1r1: &mut APB1R1) -> Crs {
+        // The CRS trims the HSI48, which has to be turned on during RCC configuration
+        assert!(clocks.hsi48());
+
+        <CRS as Enable>::enable(apb1r1);
+
+        // The configuration register may only be written while the frequency error counter
+        // is disabled
+        self.cr.modify(|_, w| w.cen().clear_bit());
+
+        self.cfgr.write(|w| unsafe {
+            w.syncsrc()
+                .bits(config.source as u8)
+                .syncpol()
+                .bit(config.polarity == SyncPolarity::Falling)
+                .syncdiv()
+                .bits(config.divider as u8)
+                .felim()
+                .bits(config.error_limit)
+                .reload()
+                .bits(config.reload)
+        });
+
+        self.cr
+            .modify(|_, w| w.autotrimen().set_bit().cen().set_bit());
+
+        Crs { crs: self }
+    }
+}
+
+/// Constrained CRS peripheral
+pub struct Crs {
+    crs: CRS,
+}
+
+impl Crs {
+    /// Returns the current HSI48 trimming value
+    pub fn trim(&self) -> u8 {
+        self.crs.cr.read().trim().bits()
+    }
+
+    /// Returns `true` if the last synchronization was within the frequency error limit
+    pub fn is_synchronized(&self) -> bool {
+        self.crs.isr.read().syncokf().bit_is_set()
+    }
+
+    /// Returns `true` if a synchronization error or miss has occurred, or if the trimming
+    /// value overflowed
+    pub fn has_error(&self) -> bool {
+        self.crs.isr.read().errf().bit_is_set()
+    }
+
+    /// Clears all status flags
+    pub fn clear_flags(&mut self) {
+        self.crs.icr.write(|w| {
+            w.syncokc()
+                .set_bit()
+                .syncwarnc()
+                .set_bit()
+                .errc()
+                .set_bit()
+                .esyncc()
+                .set_bit()
+        });
+    }
+
+    /// Disables automatic trimming and releases the CRS peripheral
+    pub fn free(self) -> CRS {
+        self.crs
+            .cr
+            .modify(|_, w| w.autotrimen().clear_bit().cen().clear_bit());
+        self.crs
+    }
+}
@@ -122,6 +122,33 @@ generate_register!(WRP1BR, wrp1br);
 const FLASH_KEY1: u32 = 0x4567_0123;
 const FLASH_KEY2: u32 = 0xCDEF_89AB;
 
+const FLASH_PDKEY1: u32 = 0x0415_2637;
+const FLASH_PDKEY2: u32 = 0xFAFB_FCFD;
+
+impl ACR {
+    /// Configures whether the flash is powered down in low-power run mode (RUN_PD)
+    ///
+    /// This lowers the current consumption at the cost of executing from SRAM only while in
+    /// low-power run mode. The flash is always powered down in Stop modes.
+    pub fn power_down_in_low_power_run(&mut self, pdkeyr: &mut PDKEYR, power_down: bool) {
+        // RUN_PD is only writable after unlocking it via PDKEYR
+        let pdkeyr = pdkeyr.pdkeyr();
+        unsafe {
+            pdkeyr.write(|w| w.bits(FLASH_PDKEY1));
+            pdkeyr.write(|w| w.bits(FLASH_PDKEY2));
+        }
+
+        self.acr().modify(|_, w| w.run_pd().bit(power_down));
+    }
+
+    /// Configures whether the flash is powered down in low-power sleep mode (SLEEP_PD)
+    ///
+    /// This lowers the current consumption at the cost of a longer wakeup time.
+    pub fn power_down_in_low_power_sleep(&mut self, power_down: bool) {
+        self.acr().modify(|_, w| w.sleep_pd().bit(power_down));
+    }
+}
+
 impl KEYR {
     /// Unlock the flash registers via KEYR to access the flash programming
     pub fn unlock_flash<'a>(
@@ -14,6 +14,10 @@ mod partially_erased;
 pub use partially_erased::{PEPin, PartiallyErasedPin};
 mod erased;
 pub use erased::{EPin, ErasedPin};
+mod debounce;
+pub use debounce::Debounced;
+mod dynamic;
+pub use dynamic::{Dynamic, DynamicPin, PinModeError};
 
 /// Extension trait to split a GPIO peripheral in independent pins and registers
 pub trait GpioExt {
@@ -233,6 +237,29 @@ impl<const P: char> PUPDR<P> {
     }
 }
 
+/// Opaque ASCR register
+#[cfg(any(
+    // feature = "stm32l471", // missing PAC support
+    // feature = "stm32l475", // missing PAC support
+    feature = "stm32l476",
+    feature = "stm32l486",
+))]
+pub struct ASCR<const P: char> {
+    _0: (),
+}
+
+#[cfg(any(
+    // feature = "stm32l471", // missing PAC support
+    // feature = "stm32l475", // missing PAC support
+    feature = "stm32l476",
+    feature = "stm32l486",
+))]
+impl<const P: char> ASCR<P> {
+    pub(crate) fn new() -> Self {
+        Self { _0: () }
+    }
+}
+
 macro_rules! gpio {
     ($GPIOX:ident, $gpiox:ident, $PXx:ident, $port_id:literal, $extigpionr:expr, $({ $pwrenable:expr },)? [
         $($PXi:ident: ($pxi:ident, $i:expr, $MODE:ty, $HL:ident, $exticri:ident),)+
@@ -242,7 +269,14 @@ macro_rules! gpio {
             use crate::stm32::$GPIOX;
 
             use crate::rcc::{AHB2, Enable, Reset};
-            use super::{Afr, Analog, GpioExt, Pin, H8, L8, MODER, OTYPER, OSPEEDR, PUPDR};
+            use super::{Afr, Analog, Gpio, GpioExt, Pin, H8, L8, MODER, OTYPER, OSPEEDR, PUPDR};
+            #[cfg(any(
+                // feature = "stm32l471", // missing PAC support
+                // feature = "stm32l475", // missing PAC support
+                feature = "stm32l476",
+                feature = "stm32l486",
+            ))]
+            use super::ASCR;
 
             /// GPIO parts
             pub struct Parts {
@@ -258,6 +292,14 @@ macro_rules! gpio {
                 pub ospeedr: OSPEEDR<$port_id>,
                 /// Opaque PUPDR register
                 pub pupdr: PUPDR<$port_id>,
+                /// Opaque ASCR register
+                #[cfg(any(
+                    // feature = "stm32l471", // missing PAC support
+                    // feature = "stm32l475", // missing PAC support
+                    feature = "stm32l476",
+                    feature = "stm32l486",
+                ))]
+                pub ascr: ASCR<$port_id>,
                 $(
                     /// Pin
                     pub $pxi: $PXi<$MODE>,
@@ -268,6 +310,28 @@ macro_rules! gpio {
                 pub type $PXi<MODE> = Pin<MODE, $HL, $port_id, $i>;
             )+
 
+            /// Reads the input levels of all pins of the port with a single read of IDR
+            pub fn read_port() -> u16 {
+                // NOTE(unsafe) atomic read with no side effects
+                unsafe { (*Gpio::<$port_id>::ptr()).idr.read().bits() as u16 }
+            }
+
+            /// Sets the outputs of the pins in `mask` to the corresponding bits of `value` with a
+            /// single atomic write to BSRR
+            ///
+            /// This does not take ownership of the pins, callers must make sure to only touch
+            /// pins which are configured as outputs and not driven through their `Pin` elsewhere.
+            pub fn write_port(mask: u16, value: u16) {
+                let set = mask & value;
+                let reset = mask & !value;
+                // NOTE(unsafe) atomic write to a stateless register
+                unsafe {
+                    (*Gpio::<$port_id>::ptr())
+                        .bsrr
+                        .write(|w| w.bits((u32::from(reset) << 16) | u32::from(set)))
+                };
+            }
+
             impl GpioExt for $GPIOX {
                 type Parts = Parts;
 
@@ -283,6 +347,13 @@ macro_rules! gpio {
                         otyper: OTYPER::new(),
                         ospeedr: OSPEEDR::new(),
                         pupdr: PUPDR::new(),
+                        #[cfg(any(
+                            // feature = "stm32l471", // missing PAC support
+                            // feature = "stm32l475", // missing PAC support
+                            feature = "stm32l476",
+                            feature = "stm32l486",
+                        ))]
+                        ascr: ASCR::new(),
                         $(
                             $pxi: $PXi::new(),
                         )+
@@ -341,6 +412,35 @@ impl<MODE, HL, const P: char, const N: u8> Pin<Output<MODE>, HL, P, N> {
     }
 }
 
+#[cfg(any(
+    // feature = "stm32l471", // missing PAC support
+    // feature = "stm32l475", // missing PAC support
+    feature = "stm32l476",
+    feature = "stm32l486",
+))]
+impl<HL, const P: char, const N: u8> Pin<Analog, HL, P, N> {
+    /// Closes the analog switch connecting the pin to the ADC input (ASCR)
+    ///
+    /// On STM32L47x/L48x, the analog switch must be closed for the signal of a pin in analog
+    /// mode to reach the ADC.
+    pub fn connect_adc(&mut self, _ascr: &mut ASCR<P>) {
+        unsafe {
+            (*Gpio::<P>::ptr())
+                .ascr
+                .modify(|r, w| w.bits(r.bits() | (1 << N)))
+        };
+    }
+
+    /// Opens the analog switch connecting the pin to the ADC input
+    pub fn disconnect_adc(&mut self, _ascr: &mut ASCR<P>) {
+        unsafe {
+            (*Gpio::<P>::ptr())
+                .ascr
+                .modify(|r, w| w.bits(r.bits() & !(1 << N)))
+        };
+    }
+}
+
 impl<HL, const P: char, const N: u8> Pin<Output<OpenDrain>, HL, P, N> {
     /// Enables / disables the internal pull up
     pub fn internal_pull_up(&mut self, _pupdr: &mut PUPDR<P>, on: bool) {
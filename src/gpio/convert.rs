@@ -158,7 +158,7 @@ impl<MODE, HL, const P: char, const N: u8> Pin<MODE, HL, P, N> {
     /// This violates the type state constraints from `MODE`, so callers must
     /// ensure they use this properly.
     #[inline(always)]
-    fn mode<M: PinMode>(&mut self) {
+    pub(super) fn mode<M: PinMode>(&mut self) {
         let offset = 2 * N;
         unsafe {
             (*Gpio::<P>::ptr())
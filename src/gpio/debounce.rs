@@ -0,0 +1,64 @@
+use super::*;
+
+/// Debounced input, e.g. for a mechanical button
+///
+/// The pin is sampled by calling [`update`](Debounced::update) periodically, e.g. from a timer
+/// interrupt, and the debounced state only changes after `samples` consecutive samples differed
+/// from it. With a 1 kHz timer and 10 samples, a state change is reported after 10 ms.
+pub struct Debounced<PIN> {
+    pin: PIN,
+    pressed: PinState,
+    samples: u8,
+    count: u8,
+    state: bool,
+}
+
+impl<PIN> Debounced<PIN>
+where
+    PIN: InputPin,
+{
+    /// Wraps `pin`, which reads `pressed` while the button is pressed, e.g. `PinState::Low`
+    /// for a button pulling an input with a pull-up to ground
+    pub fn new(pin: PIN, pressed: PinState, samples: u8) -> Self {
+        Self {
+            pin,
+            pressed,
+            samples,
+            count: 0,
+            state: false,
+        }
+    }
+
+    /// Samples the pin once, returns `true` if the debounced state changed
+    pub fn update(&mut self) -> Result<bool, PIN::Error> {
+        let sample = match self.pressed {
+            PinState::High => self.pin.is_high()?,
+            PinState::Low => self.pin.is_low()?,
+        };
+
+        if sample == self.state {
+            self.count = 0;
+            return Ok(false);
+        }
+
+        self.count += 1;
+        if self.count < self.samples {
+            return Ok(false);
+        }
+
+        self.state = sample;
+        self.count = 0;
+
+        Ok(true)
+    }
+
+    /// Returns the debounced state
+    pub fn is_pressed(&self) -> bool {
+        self.state
+    }
+
+    /// Releases the pin
+    pub fn free(self) -> PIN {
+        self.pin
+    }
+}
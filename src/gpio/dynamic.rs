@@ -0,0 +1,186 @@
+use super::*;
+
+/// Pin type with a mode that can be changed at runtime
+///
+/// - `HL` represents high 8 or low 8 pin.
+/// - `P` is port name: `A` for GPIOA, `B` for GPIOB, etc.
+/// - `N` is pin number: from `0` to `15`.
+pub struct DynamicPin<HL, const P: char, const N: u8> {
+    mode: Dynamic,
+    _hl: PhantomData<HL>,
+}
+
+/// Current mode of a [`DynamicPin`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dynamic {
+    InputFloating,
+    InputPullUp,
+    InputPullDown,
+    OutputPushPull,
+    OutputOpenDrain,
+}
+
+/// Error for operations not supported in the current mode of a [`DynamicPin`]
+#[derive(Debug, PartialEq)]
+pub enum PinModeError {
+    IncorrectMode,
+}
+
+impl Dynamic {
+    /// Returns `true` if the input level can be read in this mode
+    ///
+    /// Besides the input modes, this includes `OutputOpenDrain`, whose input level reflects the
+    /// line driven by other devices while the output is released.
+    pub fn is_readable(&self) -> bool {
+        use Dynamic::*;
+        match self {
+            InputFloating | InputPullUp | InputPullDown | OutputOpenDrain => true,
+            OutputPushPull => false,
+        }
+    }
+
+    /// Returns `true` if the output can be set in this mode
+    pub fn is_output(&self) -> bool {
+        use Dynamic::*;
+        match self {
+            InputFloating | InputPullUp | InputPullDown => false,
+            OutputPushPull | OutputOpenDrain => true,
+        }
+    }
+}
+
+impl<MODE, HL, const P: char, const N: u8> Pin<MODE, HL, P, N> {
+    /// Configures the pin as a floating input whose mode can be changed at runtime
+    pub fn into_dynamic(self, moder: &mut MODER<P>, pupdr: &mut PUPDR<P>) -> DynamicPin<HL, P, N> {
+        self.into_floating_input(moder, pupdr);
+        DynamicPin {
+            mode: Dynamic::InputFloating,
+            _hl: PhantomData,
+        }
+    }
+}
+
+impl<HL, const P: char, const N: u8> DynamicPin<HL, P, N> {
+    /// Returns the current mode
+    pub fn mode(&self) -> Dynamic {
+        self.mode
+    }
+
+    fn pin<MODE>(&self) -> Pin<MODE, HL, P, N> {
+        Pin::new()
+    }
+
+    /// Configures the pin as a floating input
+    pub fn make_floating_input(&mut self, _moder: &mut MODER<P>, _pupdr: &mut PUPDR<P>) {
+        self.pin::<Analog>().mode::<Input<Floating>>();
+        self.mode = Dynamic::InputFloating;
+    }
+
+    /// Configures the pin as a pulled up input
+    pub fn make_pull_up_input(&mut self, _moder: &mut MODER<P>, _pupdr: &mut PUPDR<P>) {
+        self.pin::<Analog>().mode::<Input<PullUp>>();
+        self.mode = Dynamic::InputPullUp;
+    }
+
+    /// Configures the pin as a pulled down input
+    pub fn make_pull_down_input(&mut self, _moder: &mut MODER<P>, _pupdr: &mut PUPDR<P>) {
+        self.pin::<Analog>().mode::<Input<PullDown>>();
+        self.mode = Dynamic::InputPullDown;
+    }
+
+    /// Configures the pin as a push pull output
+    pub fn make_push_pull_output(&mut self, _moder: &mut MODER<P>, _otyper: &mut OTYPER<P>) {
+        self.pin::<Analog>().mode::<Output<PushPull>>();
+        self.mode = Dynamic::OutputPushPull;
+    }
+
+    /// Configures the pin as a push pull output, with the output set to `state` before the
+    /// mode change to avoid a glitch
+    pub fn make_push_pull_output_in_state(
+        &mut self,
+        _moder: &mut MODER<P>,
+        _otyper: &mut OTYPER<P>,
+        state: PinState,
+    ) {
+        let mut pin = self.pin::<Analog>();
+        pin._set_state(state);
+        pin.mode::<Output<PushPull>>();
+        self.mode = Dynamic::OutputPushPull;
+    }
+
+    /// Configures the pin as an open drain output
+    pub fn make_open_drain_output(&mut self, _moder: &mut MODER<P>, _otyper: &mut OTYPER<P>) {
+        self.pin::<Analog>().mode::<Output<OpenDrain>>();
+        self.mode = Dynamic::OutputOpenDrain;
+    }
+
+    /// Configures the pin as an open drain output, with the output set to `state` before the
+    /// mode change to avoid a glitch
+    pub fn make_open_drain_output_in_state(
+        &mut self,
+        _moder: &mut MODER<P>,
+        _otyper: &mut OTYPER<P>,
+        state: PinState,
+    ) {
+        let mut pin = self.pin::<Analog>();
+        pin._set_state(state);
+        pin.mode::<Output<OpenDrain>>();
+        self.mode = Dynamic::OutputOpenDrain;
+    }
+
+    /// Drives the pin high, fails if the pin is not configured as an output
+    pub fn set_high(&mut self) -> Result<(), PinModeError> {
+        self.set_state(PinState::High)
+    }
+
+    /// Drives the pin low, fails if the pin is not configured as an output
+    pub fn set_low(&mut self) -> Result<(), PinModeError> {
+        self.set_state(PinState::Low)
+    }
+
+    /// Drives the pin to `state`, fails if the pin is not configured as an output
+    pub fn set_state(&mut self, state: PinState) -> Result<(), PinModeError> {
+        if self.mode.is_output() {
+            self.pin::<Analog>()._set_state(state);
+            Ok(())
+        } else {
+            Err(PinModeError::IncorrectMode)
+        }
+    }
+
+    /// Returns whether the input level is high, fails if the level can not be read in the
+    /// current mode, see [`Dynamic::is_readable`]
+    pub fn is_high(&self) -> Result<bool, PinModeError> {
+        self.is_low().map(|b| !b)
+    }
+
+    /// Returns whether the input level is low, fails if the level can not be read in the
+    /// current mode, see [`Dynamic::is_readable`]
+    pub fn is_low(&self) -> Result<bool, PinModeError> {
+        if self.mode.is_readable() {
+            Ok(self.pin::<Analog>()._is_low())
+        } else {
+            Err(PinModeError::IncorrectMode)
+        }
+    }
+}
+
+impl<HL, const P: char, const N: u8> OutputPin for DynamicPin<HL, P, N> {
+    type Error = PinModeError;
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set_high()
+    }
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set_low()
+    }
+}
+
+impl<HL, const P: char, const N: u8> InputPin for DynamicPin<HL, P, N> {
+    type Error = PinModeError;
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.is_high()
+    }
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.is_low()
+    }
+}
@@ -1,8 +1,14 @@
 //! Inter-Integrated Circuit (I2C) bus. Synchronized with the
 //! [stm32h7xx-hal](https://github.com/stm32-rs/stm32h7xx-hal) implementation,
 //! as of 2021-02-25.
+//!
+//! The driver only supports master mode, so wakeup from Stop mode (WUPEN), which requires an own
+//! address match in slave mode, is not supported. Note that only I2C3 keeps its wakeup
+//! capability in Stop 2.
 
+use crate::hal::blocking::delay::DelayUs;
 use crate::hal::blocking::i2c::{Read, Write, WriteRead};
+use crate::hal::digital::v2::OutputPin;
 
 #[cfg(any(
     feature = "stm32l451",
@@ -25,6 +31,7 @@ use crate::pac::{i2c1, I2C1, I2C2, I2C3};
 use crate::rcc::{Clocks, Enable, RccBus, Reset};
 use crate::time::Hertz;
 use cast::{u16, u8};
+use core::convert::Infallible;
 use core::ops::Deref;
 
 /// I2C error
@@ -37,9 +44,10 @@ pub enum Error {
     Arbitration,
     /// NACK
     Nack,
+    /// SCL was held low for longer than the configured timeout
+    Timeout,
     // Overrun, // slave mode only
     // Pec, // SMBUS mode only
-    // Timeout, // SMBUS mode only
     // Alert, // SMBUS mode only
 }
 
@@ -67,6 +75,34 @@ macro_rules! pins {
     }
 }
 
+/// I2C slave address
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Address {
+    /// 7-bit address
+    SevenBit(u8),
+    /// 10-bit address, must be below 0x400
+    TenBit(u16),
+}
+
+impl Address {
+    /// Returns the SADD field value and whether 10-bit addressing is used
+    fn sadd(self) -> (u16, bool) {
+        match self {
+            Address::SevenBit(addr) => (u16(addr) << 1, false),
+            Address::TenBit(addr) => {
+                assert!(addr < 1 << 10);
+                (addr, true)
+            }
+        }
+    }
+}
+
+impl From<u8> for Address {
+    fn from(addr: u8) -> Self {
+        Address::SevenBit(addr)
+    }
+}
+
 /// I2C peripheral operating in master mode
 pub struct I2c<I2C, PINS> {
     i2c: I2C,
@@ -79,6 +115,7 @@ pub struct Config {
     scll: u8,
     scldel: u8,
     sdadel: u8,
+    timeout: Option<u16>,
 }
 
 impl Config {
@@ -160,6 +197,7 @@ impl Config {
             scll,
             scldel,
             sdadel,
+            timeout: None,
         }
     }
 
@@ -171,8 +209,19 @@ impl Config {
             sdadel: ((timing_bits >> 16) & 0xf) as u8,
             sclh: ((timing_bits >> 8) & 0xff) as u8,
             scll: (timing_bits & 0xff) as u8,
+            timeout: None,
         }
     }
+
+    /// Enable the SCL low timeout, which aborts a transfer with `Error::Timeout` when a slave
+    /// holds SCL low for `(timeout + 1) * 2048 * t_I2CCLK`
+    ///
+    /// Note that it only takes 12 bits, using more than this will cause a panic.
+    pub fn with_timeout(mut self, timeout: u16) -> Self {
+        assert!(timeout < 1 << 12);
+        self.timeout = Some(timeout);
+        self
+    }
 }
 
 macro_rules! hal {
@@ -243,6 +292,18 @@ where
                 .bits(config.scldel)
         });
 
+        // Configure the SCL low timeout
+        if let Some(timeout) = config.timeout {
+            i2c.timeoutr.write(|w| {
+                w.timeouta()
+                    .bits(timeout)
+                    .tidle()
+                    .clear_bit()
+                    .timouten()
+                    .set_bit()
+            });
+        }
+
         // Enable the peripheral
         i2c.cr1.write(|w| w.pe().set_bit());
 
@@ -255,6 +316,39 @@ where
     }
 }
 
+/// Frees a slave holding SDA low by clocking nine pulses on SCL, followed by a STOP condition
+///
+/// The pins have to be released from the I2C peripheral (see [`I2c::free`]) and configured as
+/// open drain outputs. `half_period_us` is half the SCL period in microseconds, e.g. 5 for
+/// 100 kHz.
+pub fn recover_bus<SCL, SDA>(
+    scl: &mut SCL,
+    sda: &mut SDA,
+    delay: &mut impl DelayUs<u32>,
+    half_period_us: u32,
+) where
+    SCL: OutputPin<Error = Infallible>,
+    SDA: OutputPin<Error = Infallible>,
+{
+    let _ = sda.set_high();
+
+    for _ in 0..9 {
+        let _ = scl.set_low();
+        delay.delay_us(half_period_us);
+        let _ = scl.set_high();
+        delay.delay_us(half_period_us);
+    }
+
+    // STOP: SDA rises while SCL is high
+    let _ = scl.set_low();
+    let _ = sda.set_low();
+    delay.delay_us(half_period_us);
+    let _ = scl.set_high();
+    delay.delay_us(half_period_us);
+    let _ = sda.set_high();
+    delay.delay_us(half_period_us);
+}
+
 /// Sequence to flush the TXDR register. This resets the TXIS and TXE
 // flags
 macro_rules! flush_txdr {
@@ -281,6 +375,9 @@ macro_rules! busy_wait {
             } else if isr.berr().is_error() {
                 $i2c.icr.write(|w| w.berrcf().set_bit());
                 return Err(Error::Bus);
+            } else if isr.timeout().bit_is_set() {
+                $i2c.icr.write(|w| w.timoutcf().set_bit());
+                return Err(Error::Timeout);
             } else if isr.arlo().is_lost() {
                 $i2c.icr.write(|w| w.arlocf().set_bit());
                 return Err(Error::Arbitration);
@@ -295,16 +392,20 @@ macro_rules! busy_wait {
     };
 }
 
-impl<PINS, I2C> Write for I2c<I2C, PINS>
+impl<PINS, I2C> I2c<I2C, PINS>
 where
     I2C: Deref<Target = i2c1::RegisterBlock>,
 {
-    type Error = Error;
-
-    fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
+    /// Writes `bytes` to the slave at `addr`
+    ///
+    /// Unlike [`Write::write`], this also supports 10-bit addresses. Note that a 10-bit address of
+    /// 0x400 or above will cause a panic.
+    pub fn write_addr(&mut self, addr: Address, bytes: &[u8]) -> Result<(), Error> {
         // TODO support transfers of more than 255 bytes
         assert!(bytes.len() < 256);
 
+        let (sadd, add10) = addr.sadd();
+
         // Wait for any previous address sequence to end
         // automatically. This could be up to 50% of a bus
         // cycle (ie. up to 0.5/freq)
@@ -317,9 +418,9 @@ where
             w.start()
                 .set_bit()
                 .sadd()
-                .bits(u16(addr << 1 | 0))
+                .bits(sadd)
                 .add10()
-                .clear_bit()
+                .bit(add10)
                 .rd_wrn()
                 .write()
                 .nbytes()
@@ -347,18 +448,17 @@ where
         Ok(())
         // Tx::new(&self.i2c)?.write(addr, bytes)
     }
-}
-
-impl<PINS, I2C> Read for I2c<I2C, PINS>
-where
-    I2C: Deref<Target = i2c1::RegisterBlock>,
-{
-    type Error = Error;
 
-    fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Error> {
+    /// Reads enough bytes from the slave at `addr` to fill `buffer`
+    ///
+    /// Unlike [`Read::read`], this also supports 10-bit addresses. Note that a 10-bit address of
+    /// 0x400 or above will cause a panic.
+    pub fn read_addr(&mut self, addr: Address, buffer: &mut [u8]) -> Result<(), Error> {
         // TODO support transfers of more than 255 bytes
         assert!(buffer.len() < 256 && buffer.len() > 0);
 
+        let (sadd, add10) = addr.sadd();
+
         // Wait for any previous address sequence to end
         // automatically. This could be up to 50% of a bus
         // cycle (ie. up to 0.5/freq)
@@ -366,10 +466,17 @@ where
 
         // Set START and prepare to receive bytes into
         // `buffer`. The START bit can be set even if the bus
-        // is BUSY or I2C is in slave mode.
+        // is BUSY or I2C is in slave mode. For 10-bit
+        // addresses, the complete address is sent in write
+        // direction, followed by a reSTART with the header
+        // in read direction.
         self.i2c.cr2.write(|w| {
             w.sadd()
-                .bits((addr << 1 | 0) as u16)
+                .bits(sadd)
+                .add10()
+                .bit(add10)
+                .head10r()
+                .complete()
                 .rd_wrn()
                 .read()
                 .nbytes()
@@ -392,19 +499,24 @@ where
         Ok(())
         // Rx::new(&self.i2c)?.read(addr, buffer)
     }
-}
-
-impl<PINS, I2C> WriteRead for I2c<I2C, PINS>
-where
-    I2C: Deref<Target = i2c1::RegisterBlock>,
-{
-    type Error = Error;
 
-    fn write_read(&mut self, addr: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), Error> {
+    /// Writes `bytes` to the slave at `addr`, then reads enough bytes to fill
+    /// `buffer` in a single transaction
+    ///
+    /// Unlike [`WriteRead::write_read`], this also supports 10-bit addresses. Note that a 10-bit address of
+    /// 0x400 or above will cause a panic.
+    pub fn write_read_addr(
+        &mut self,
+        addr: Address,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Error> {
         // TODO support transfers of more than 255 bytes
         assert!(bytes.len() < 256 && bytes.len() > 0);
         assert!(buffer.len() < 256 && buffer.len() > 0);
 
+        let (sadd, add10) = addr.sadd();
+
         // Wait for any previous address sequence to end
         // automatically. This could be up to 50% of a bus
         // cycle (ie. up to 0.5/freq)
@@ -417,9 +529,9 @@ where
             w.start()
                 .set_bit()
                 .sadd()
-                .bits(u16(addr << 1 | 0))
+                .bits(sadd)
                 .add10()
-                .clear_bit()
+                .bit(add10)
                 .rd_wrn()
                 .write()
                 .nbytes()
@@ -440,12 +552,16 @@ where
         // Wait until the write finishes before beginning to read.
         busy_wait!(self.i2c, tc, is_complete);
 
-        // reSTART and prepare to receive bytes into `buffer`
+        // reSTART and prepare to receive bytes into `buffer`. For
+        // 10-bit addresses, the slave was already addressed in
+        // write direction, so only the header is sent.
         self.i2c.cr2.write(|w| {
             w.sadd()
-                .bits(u16(addr << 1 | 1))
+                .bits(sadd)
                 .add10()
-                .clear_bit()
+                .bit(add10)
+                .head10r()
+                .partial()
                 .rd_wrn()
                 .read()
                 .nbytes()
@@ -467,6 +583,39 @@ where
     }
 }
 
+impl<PINS, I2C> Write for I2c<I2C, PINS>
+where
+    I2C: Deref<Target = i2c1::RegisterBlock>,
+{
+    type Error = Error;
+
+    fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
+        self.write_addr(Address::SevenBit(addr), bytes)
+    }
+}
+
+impl<PINS, I2C> Read for I2c<I2C, PINS>
+where
+    I2C: Deref<Target = i2c1::RegisterBlock>,
+{
+    type Error = Error;
+
+    fn read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Error> {
+        self.read_addr(Address::SevenBit(addr), buffer)
+    }
+}
+
+impl<PINS, I2C> WriteRead for I2c<I2C, PINS>
+where
+    I2C: Deref<Target = i2c1::RegisterBlock>,
+{
+    type Error = Error;
+
+    fn write_read(&mut self, addr: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), Error> {
+        self.write_read_addr(Address::SevenBit(addr), bytes, buffer)
+    }
+}
+
 #[cfg(any(feature = "stm32l431", feature = "stm32l451", feature = "stm32l471"))]
 mod stm32l4x1_pins {
     #[cfg(any(feature = "stm32l451"))]
@@ -127,6 +127,8 @@ pub mod adc;
 #[cfg(not(any(feature = "stm32l412",)))]
 pub mod can;
 pub mod crc;
+#[cfg(not(any(feature = "stm32l475",)))]
+pub mod crs;
 pub mod datetime;
 pub mod delay;
 pub mod dma;
@@ -165,7 +165,7 @@ pub struct C3;
 pub struct C4;
 
 macro_rules! advanced_timer {
-    ($($TIMX:ident: ($timX:ident, $apb:ident, $psc_width:ident, $arr_width:ident),)+) => {
+    ($($TIMX:ident: ($timX:ident, $apb:ident, $timclk:ident, $psc_width:ident, $arr_width:ident),)+) => {
         $(
             fn $timX<PINS>(
                 tim: $TIMX,
@@ -196,7 +196,7 @@ macro_rules! advanced_timer {
                     tim.ccmr2_output().modify(|_, w| w.oc4pe().set_bit().oc4m().bits(6));
                 }
 
-                let clk = clocks.pclk2();
+                let clk = clocks.$timclk();
                 let ticks = clk / freq;
 
                 // maybe this is all u32? also, why no `- 1` vs `timer.rs`?
@@ -233,7 +233,7 @@ macro_rules! advanced_timer {
 }
 
 macro_rules! standard_timer {
-    ($($TIMX:ident: ($timX:ident, $apb:ident, $psc_width:ident, $arr_width:ident),)+) => {
+    ($($TIMX:ident: ($timX:ident, $apb:ident, $timclk:ident, $psc_width:ident, $arr_width:ident),)+) => {
         $(
             fn $timX<PINS>(
                 tim: $TIMX,
@@ -264,7 +264,7 @@ macro_rules! standard_timer {
                     tim.ccmr2_output().modify(|_, w| w.oc4pe().set_bit().oc4m().bits(6));
                 }
 
-                let clk = clocks.pclk1();
+                let clk = clocks.$timclk();
                 let ticks = clk / freq;
 
                 // maybe this is all u32? also, why no `- 1` vs `timer.rs`?
@@ -297,7 +297,7 @@ macro_rules! standard_timer {
 }
 
 macro_rules! small_timer {
-    ($($TIMX:ident: ($timX:ident, $apb:ident, $psc_width:ident, $arr_width:ident),)+) => {
+    ($($TIMX:ident: ($timX:ident, $apb:ident, $timclk:ident, $psc_width:ident, $arr_width:ident),)+) => {
         $(
             fn $timX<PINS>(
                 tim: $TIMX,
@@ -321,7 +321,7 @@ macro_rules! small_timer {
                 //     tim.ccmr1_output().modify(|_, w| w.oc2pe().set_bit().oc2m().bits(6));
                 // }
 
-                let clk = clocks.pclk1();
+                let clk = clocks.$timclk();
                 let ticks = clk / freq;
 
                 // maybe this is all u32? also, why no `- 1` vs `timer.rs`?
@@ -352,6 +352,236 @@ macro_rules! small_timer {
     }
 }
 
+/// Encodes a dead-time of `ticks` DTS clock periods into the BDTR DTG field
+///
+/// Returns the DTG value together with the dead-time in DTS clock periods it actually yields,
+/// which is the closest encodable dead-time. Values beyond the longest encodable dead-time are
+/// clamped.
+fn dead_time_generator(ticks: u32) -> (u8, u32) {
+    // (DTG prefix, step, min and max multiplier) of the encodings:
+    // DTG[7:5] = 0xx: DT = DTG[7:0] x tDTS
+    // DTG[7:6] = 10: DT = (64 + DTG[5:0]) x 2 x tDTS
+    // DTG[7:5] = 110: DT = (32 + DTG[4:0]) x 8 x tDTS
+    // DTG[7:5] = 111: DT = (32 + DTG[4:0]) x 16 x tDTS
+    const RANGES: [(u8, u32, u32, u32); 4] = [
+        (0b0000_0000, 1, 0, 127),
+        (0b1000_0000, 2, 64, 127),
+        (0b1100_0000, 8, 32, 63),
+        (0b1110_0000, 16, 32, 63),
+    ];
+
+    // Round to the nearest multiple of `step` within each encoding, then pick the encoding
+    // closest to `ticks`, since the ranges overlap and leave gaps at their boundaries
+    RANGES
+        .iter()
+        .map(|&(prefix, step, min, max)| {
+            let m = (ticks / step + u32::from(ticks % step * 2 >= step)).clamp(min, max);
+            (prefix | (m - min) as u8, m * step)
+        })
+        .min_by_key(|&(_, dt)| (i64::from(dt) - i64::from(ticks)).abs())
+        .unwrap()
+}
+
+/// Clock division (CKD) between the timer clock and the dead-time and digital filter sampling
+/// clock (DTS)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CkdDiv {
+    /// tDTS = tCK_INT
+    Div1 = 0b00,
+    /// tDTS = 2 × tCK_INT
+    Div2 = 0b01,
+    /// tDTS = 4 × tCK_INT
+    Div4 = 0b10,
+}
+
+macro_rules! clock_division {
+    ($($TIMX:ident,)+) => {
+        $(
+            impl<CHANNEL> Pwm<$TIMX, CHANNEL> {
+                /// Sets the clock division (CKD) of the DTS clock, used by the digital filters and,
+                /// on timers with complementary outputs, the dead-time generator
+                ///
+                /// A larger division slows down the input filters and extends the range of
+                /// achievable dead-times, at the cost of resolution. The division is shared by all
+                /// channels of the timer.
+                pub fn set_clock_division(&mut self, div: CkdDiv) {
+                    let tim = unsafe { &*$TIMX::ptr() };
+                    tim.cr1.modify(|_, w| unsafe { w.ckd().bits(div as u8) });
+                }
+            }
+        )+
+    }
+}
+
+macro_rules! dead_time {
+    ($($TIMX:ident,)+) => {
+        $(
+            impl<CHANNEL> Pwm<$TIMX, CHANNEL> {
+                /// Sets the dead-time inserted between complementary outputs, in nanoseconds
+                ///
+                /// The dead-time is shared by all channels of the timer. It is derived from the
+                /// DTS clock, i.e. the timer clock divided by the clock division (CKD), and its
+                /// encoding gets coarser for longer dead-times. Returns the dead-time actually
+                /// achieved, rounded to the nearest nanosecond, which is clamped to the longest
+                /// possible dead-time.
+                ///
+                /// The dead-time has to be set again after changing the clock division with
+                /// `set_clock_division`.
+                pub fn set_dead_time(&mut self, ns: u32, clocks: &Clocks) -> u32 {
+                    let tim = unsafe { &*$TIMX::ptr() };
+
+                    let dts = clocks.timclk2().raw() >> tim.cr1.read().ckd().bits();
+                    let ticks = (u64::from(ns) * u64::from(dts) + 500_000_000) / 1_000_000_000;
+                    let (dtg, ticks) = dead_time_generator(ticks.min(u64::from(u32::MAX)) as u32);
+
+                    tim.bdtr.modify(|_, w| unsafe { w.dtg().bits(dtg) });
+
+                    // Rounded to the nearest nanosecond
+                    let dts = u64::from(dts);
+                    ((u64::from(ticks) * 1_000_000_000 + dts / 2) / dts) as u32
+                }
+            }
+        )+
+    }
+}
+
+/// Returns the compare values for `set_phase_duty` as `(wrap, ccrX, ccrY)`, where `wrap` is
+/// `true` if the active window wraps around at the end of the period of `max + 1` ticks
+fn phase_duty_compare(phase: u32, duty: u32, max: u32) -> (bool, u32, u32) {
+    if phase <= max - duty {
+        (false, phase, phase + duty)
+    } else {
+        // phase + duty - (max + 1), which can't overflow as phase > max - duty
+        (true, phase - (max - duty) - 1, phase)
+    }
+}
+
+macro_rules! phase_shift {
+    ($($TIMX:ident: ($channel:ident, $arr_width:ident, $ccmrX_output:ident, $ocXm:ident, $ocXm_3:ident, $ocXpe:ident, $ocYm:ident, $ocYm_3:ident, $ocYpe:ident, $ccrX:ident, $ccrY:ident),)+) => {
+        $(
+            impl Pwm<$TIMX, $channel> {
+                /// Sets the duty cycle together with a phase offset within the period
+                ///
+                /// The output is active for `duty` ticks starting at `phase`, wrapping around at
+                /// the end of the period, so several outputs of a timer can be spread evenly over
+                /// the period. Both values are in the range of `get_max_duty()`.
+                ///
+                /// This uses the combined PWM mode, which also takes the compare register of the
+                /// paired channel (C2 for C1, C4 for C3). The paired channel can therefore not be
+                /// used as an independent output anymore.
+                pub fn set_phase_duty(&mut self, phase: $arr_width, duty: $arr_width) {
+                    let tim = unsafe { &*$TIMX::ptr() };
+
+                    let max = tim.arr.read().arr().bits();
+                    assert!(phase <= max && duty <= max);
+
+                    let (wrap, ccr_x, ccr_y) =
+                        phase_duty_compare(phase as u32, duty as u32, max as u32);
+                    // The compare values are at most `max`
+                    let (ccr_x, ccr_y) = (ccr_x as $arr_width, ccr_y as $arr_width);
+
+                    if !wrap {
+                        // Active in [phase, phase + duty): combined PWM mode 2, i.e. the AND of
+                        // this channel in PWM mode 2 and the paired channel in PWM mode 1
+                        tim.$ccrX.write(|w| w.ccr().bits(ccr_x));
+                        tim.$ccrY.write(|w| w.ccr().bits(ccr_y));
+                        tim.$ccmrX_output().modify(|_, w| {
+                            w.$ocXpe()
+                                .set_bit()
+                                .$ocXm()
+                                .bits(0b101)
+                                .$ocXm_3()
+                                .set_bit()
+                                .$ocYpe()
+                                .set_bit()
+                                .$ocYm()
+                                .bits(0b110)
+                                .$ocYm_3()
+                                .clear_bit()
+                        });
+                    } else {
+                        // Active in [0, phase + duty - (max + 1)) and [phase, max]: combined PWM
+                        // mode 1, i.e. the OR of this channel in PWM mode 1 and the paired channel
+                        // in PWM mode 2
+                        tim.$ccrX.write(|w| w.ccr().bits(ccr_x));
+                        tim.$ccrY.write(|w| w.ccr().bits(ccr_y));
+                        tim.$ccmrX_output().modify(|_, w| {
+                            w.$ocXpe()
+                                .set_bit()
+                                .$ocXm()
+                                .bits(0b100)
+                                .$ocXm_3()
+                                .set_bit()
+                                .$ocYpe()
+                                .set_bit()
+                                .$ocYm()
+                                .bits(0b111)
+                                .$ocYm_3()
+                                .clear_bit()
+                        });
+                    }
+                }
+            }
+        )+
+    }
+}
+
+/// Polarity of the external trigger (ETR) input
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EtrPolarity {
+    /// Active on a high level or rising edge
+    NonInverted,
+    /// Active on a low level or falling edge
+    Inverted,
+}
+
+macro_rules! etr_clear {
+    ($($TIMX:ident: [$(($channel:ident, $ccmrX_output:ident, $ocXce:ident),)+],)+) => {
+        $(
+            impl<CHANNEL> Pwm<$TIMX, CHANNEL> {
+                /// Routes the filtered external trigger (ETRF) to the output compare clear
+                /// input (OCREF_CLR)
+                ///
+                /// Channels with the clear enabled by `set_etr_clear` are forced inactive while
+                /// ETRF is active and resume at the next update event, which provides a
+                /// cycle-by-cycle limit, e.g. from an overcurrent comparator, without latching
+                /// like the break input. `filter` is the ETF digital filter setting, sampled with
+                /// the DTS clock (see `set_clock_division`). The ETR pin has to be configured in
+                /// its alternate function mode by the user.
+                ///
+                /// Note that `filter` takes 4 bits, using more than this will cause a panic.
+                pub fn configure_etr_clear(&mut self, polarity: EtrPolarity, filter: u8) {
+                    assert!(filter < 1 << 4);
+
+                    let tim = unsafe { &*$TIMX::ptr() };
+
+                    // OCCS (bit 3) is missing from the SVD, it selects ETRF as OCREF_CLR source
+                    tim.smcr.modify(|r, w| unsafe {
+                        w.bits(r.bits() | (1 << 3))
+                            .etp()
+                            .bit(polarity == EtrPolarity::Inverted)
+                            .etps()
+                            .bits(0b00)
+                            .etf()
+                            .bits(filter)
+                    });
+                }
+            }
+
+            $(
+                impl Pwm<$TIMX, $channel> {
+                    /// Enables or disables clearing this channel's output on an active external
+                    /// trigger, see `configure_etr_clear`
+                    pub fn set_etr_clear(&mut self, enable: bool) {
+                        let tim = unsafe { &*$TIMX::ptr() };
+                        tim.$ccmrX_output().modify(|_, w| w.$ocXce().bit(enable));
+                    }
+                }
+            )+
+        )+
+    }
+}
+
 macro_rules! pwm_channels {
     ($TIMX:ident: $(($channel:ident, $arr_width:ident, $ccXe:ident, $ccrX:ident, $ccr:ident),)+) => {
         $(
@@ -388,13 +618,90 @@ macro_rules! pwm_channels {
 }
 
 advanced_timer! {
-    TIM1: (tim1, APB2, u16, u16),
+    TIM1: (tim1, APB2, timclk2, u16, u16),
+}
+
+dead_time! {
+    TIM1,
+    TIM15,
+}
+
+clock_division! {
+    TIM1,
+    TIM2,
+    TIM15,
+}
+
+etr_clear! {
+    TIM1: [
+        (C1, ccmr1_output, oc1ce),
+        (C2, ccmr1_output, oc2ce),
+        (C3, ccmr2_output, oc3ce),
+        (C4, ccmr2_output, oc4ce),
+    ],
+    TIM2: [
+        (C1, ccmr1_output, oc1ce),
+        (C2, ccmr1_output, oc2ce),
+        (C3, ccmr2_output, oc3ce),
+        (C4, ccmr2_output, oc4ce),
+    ],
+}
+
+phase_shift! {
+    TIM1: (C1, u16, ccmr1_output, oc1m, oc1m_3, oc1pe, oc2m, oc2m_3, oc2pe, ccr1, ccr2),
+    TIM1: (C3, u16, ccmr2_output, oc3m, oc3m_3, oc3pe, oc4m, oc4m_3, oc4pe, ccr3, ccr4),
+    TIM2: (C1, u32, ccmr1_output, oc1m, oc1m_3, oc1pe, oc2m, oc2m_3, oc2pe, ccr1, ccr2),
+    TIM2: (C3, u32, ccmr2_output, oc3m, oc3m_3, oc3pe, oc4m, oc4m_3, oc4pe, ccr3, ccr4),
 }
 
 standard_timer! {
-    TIM2: (tim2, APB1R1, u16, u32),
+    TIM2: (tim2, APB1R1, timclk1, u16, u32),
 }
 
 small_timer! {
-    TIM15: (tim15, APB2, u16, u16),
+    TIM15: (tim15, APB2, timclk2, u16, u16),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::phase_duty_compare;
+
+    /// Number of ticks per period the combined PWM output is active
+    fn high_ticks(phase: u32, duty: u32, max: u32) -> u32 {
+        let (wrap, ccr_x, ccr_y) = phase_duty_compare(phase, duty, max);
+        (0..=max)
+            .filter(|&cnt| {
+                if wrap {
+                    // OR of PWM mode 1 and PWM mode 2
+                    cnt < ccr_x || cnt >= ccr_y
+                } else {
+                    // AND of PWM mode 2 and PWM mode 1
+                    cnt >= ccr_x && cnt < ccr_y
+                }
+            })
+            .count() as u32
+    }
+
+    #[test]
+    fn phase_duty_high_time() {
+        // Without wrap-around
+        assert_eq!(phase_duty_compare(10, 50, 99), (false, 10, 60));
+        assert_eq!(high_ticks(10, 50, 99), 50);
+        assert_eq!(high_ticks(0, 99, 99), 99);
+
+        // With wrap-around
+        assert_eq!(phase_duty_compare(50, 50, 99), (true, 0, 50));
+        assert_eq!(high_ticks(50, 50, 99), 50);
+        assert_eq!(phase_duty_compare(60, 50, 99), (true, 10, 60));
+        assert_eq!(high_ticks(60, 50, 99), 50);
+        assert_eq!(high_ticks(99, 99, 99), 99);
+    }
+
+    #[test]
+    fn phase_duty_no_overflow() {
+        assert_eq!(
+            phase_duty_compare(u32::MAX, u32::MAX, u32::MAX),
+            (true, u32::MAX - 1, u32::MAX)
+        );
+    }
 }
@@ -1,7 +1,45 @@
 //! Power management
 
+use crate::gpio::{Edge, Pin};
+use crate::hal::timer::CountDown;
 use crate::rcc::{Enable, APB1R1};
-use crate::stm32::{pwr, PWR};
+use crate::rtc::{self, Rtc};
+use crate::stm32::{pwr, EXTI, PWR};
+use core::fmt;
+#[cfg(any(
+    // feature = "stm32l4p5",
+    // feature = "stm32l4q5",
+    // feature = "stm32l4r5",
+    // feature = "stm32l4s5",
+    // feature = "stm32l4r7",
+    // feature = "stm32l4s7",
+    feature = "stm32l4r9",
+    feature = "stm32l4s9",
+))]
+use core::ptr;
+use cortex_m::asm;
+use cortex_m::peripheral::SCB;
+
+/// Power management error
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    /// The regulator did not become ready in time
+    RegulatorTimeout,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::RegulatorTimeout => f.write_str("regulator did not become ready in time"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// Number of status register polls before giving up on the regulator
+const REGULATOR_TIMEOUT: u32 = 100_000;
 
 pub struct Pwr {
     pub cr1: CR1,
@@ -29,14 +67,620 @@ impl PwrExt for PWR {
     }
 }
 
+/// Voltage scaling range of the main regulator (VOS)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VosRange {
+    /// Range 1, high performance, the reset default
+    Range1 = 0b01,
+    /// Range 2, low power, the system clock must not exceed 26 MHz
+    Range2 = 0b10,
+}
+
+/// Stop mode variants, ordered by increasing power savings and wakeup latency
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StopMode {
+    /// Stop 0, the main regulator stays on
+    Stop0 = 0b000,
+    /// Stop 1, the low-power regulator is used
+    Stop1 = 0b001,
+    /// Stop 2, most of the core domain is powered down
+    Stop2 = 0b010,
+}
+
+/// Programmable voltage detector (PVD) thresholds, the falling edge threshold of VDD
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PvdLevel {
+    /// VPVD0, around 2.0 V
+    V2_0 = 0b000,
+    /// VPVD1, around 2.2 V
+    V2_2 = 0b001,
+    /// VPVD2, around 2.4 V
+    V2_4 = 0b010,
+    /// VPVD3, around 2.5 V
+    V2_5 = 0b011,
+    /// VPVD4, around 2.6 V
+    V2_6 = 0b100,
+    /// VPVD5, around 2.8 V
+    V2_8 = 0b101,
+    /// VPVD6, around 2.9 V
+    V2_9 = 0b110,
+}
+
+/// VBAT battery charging resistor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChargeResistor {
+    /// 5 kΩ
+    R5k,
+    /// 1.5 kΩ
+    R1_5k,
+}
+
+/// EXTI line connected to the PVD output
+const PVD_EXTI_LINE: u8 = 16;
+
+/// Wakeup pins, which can wake the device from Standby and Shutdown
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WakeupPin {
+    /// WKUP1, PA0
+    Pin1,
+    /// WKUP2, PC13
+    Pin2,
+    /// WKUP3, PE6
+    Pin3,
+    /// WKUP4, PA2
+    Pin4,
+    /// WKUP5, PC5
+    Pin5,
+}
+
+/// Wakeup pin edge waking the device
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WakeupPolarity {
+    /// Wake up on the rising edge, i.e. a high level, the reset default
+    High,
+    /// Wake up on the falling edge, i.e. a low level, e.g. for active-low buttons
+    Low,
+}
+
+/// Pull applied to an I/O while in Standby or Shutdown
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StandbyPull {
+    /// No pull, the I/O floats
+    Floating,
+    /// Pull-up
+    PullUp,
+    /// Pull-down
+    PullDown,
+}
+
+impl Pwr {
+    /// Enables waking up from Standby and Shutdown through `pin` on the given `polarity`
+    ///
+    /// The wakeup pin flags are cleared when entering Standby or Shutdown, so a pin which is
+    /// already at its active level wakes the device immediately.
+    pub fn enable_wakeup_pin(&mut self, pin: WakeupPin, polarity: WakeupPolarity) {
+        let low = polarity == WakeupPolarity::Low;
+        self.cr4.reg().modify(|_, w| match pin {
+            WakeupPin::Pin1 => w.wp1().bit(low),
+            WakeupPin::Pin2 => w.wp2().bit(low),
+            WakeupPin::Pin3 => w.wp3().bit(low),
+            WakeupPin::Pin4 => w.wp4().bit(low),
+            WakeupPin::Pin5 => w.wp5().bit(low),
+        });
+        self.cr3.reg().modify(|_, w| match pin {
+            WakeupPin::Pin1 => w.ewup1().set_bit(),
+            WakeupPin::Pin2 => w.ewup2().set_bit(),
+            WakeupPin::Pin3 => w.ewup3().set_bit(),
+            WakeupPin::Pin4 => w.ewup4().set_bit(),
+            WakeupPin::Pin5 => w.ewup5().set_bit(),
+        });
+    }
+
+    /// Disables waking up from Standby and Shutdown through `pin`
+    pub fn disable_wakeup_pin(&mut self, pin: WakeupPin) {
+        self.cr3.reg().modify(|_, w| match pin {
+            WakeupPin::Pin1 => w.ewup1().clear_bit(),
+            WakeupPin::Pin2 => w.ewup2().clear_bit(),
+            WakeupPin::Pin3 => w.ewup3().clear_bit(),
+            WakeupPin::Pin4 => w.ewup4().clear_bit(),
+            WakeupPin::Pin5 => w.ewup5().clear_bit(),
+        });
+    }
+
+    /// Returns `true` if a wakeup event occurred on `pin` (WUFx)
+    ///
+    /// The flag is kept until it is cleared with [`Pwr::clear_wakeup_flags`], also across a
+    /// wakeup from Standby, so this can be used to find the wakeup source after reset.
+    pub fn is_wakeup_flag_set(&self, pin: WakeupPin) -> bool {
+        // NOTE(unsafe) read-only status register
+        let sr1 = unsafe { (*PWR::ptr()).sr1.read() };
+
+        // NOTE the SVD swaps the field names of SR1 and SCR, these are WUF1-5
+        match pin {
+            WakeupPin::Pin1 => sr1.cwuf1().bit_is_set(),
+            WakeupPin::Pin2 => sr1.cwuf2().bit_is_set(),
+            WakeupPin::Pin3 => sr1.cwuf3().bit_is_set(),
+            WakeupPin::Pin4 => sr1.cwuf4().bit_is_set(),
+            WakeupPin::Pin5 => sr1.cwuf5().bit_is_set(),
+        }
+    }
+
+    /// Clears the wakeup flags of all wakeup pins (WUF1-5)
+    pub fn clear_wakeup_flags(&mut self) {
+        // NOTE(unsafe) write-only clear register
+        // NOTE the SVD swaps the field names of SR1 and SCR, these are CWUF1-5
+        unsafe {
+            (*PWR::ptr()).scr.write(|w| {
+                w.wuf1()
+                    .set_bit()
+                    .wuf2()
+                    .set_bit()
+                    .wuf3()
+                    .set_bit()
+                    .wuf4()
+                    .set_bit()
+                    .wuf5()
+                    .set_bit()
+            })
+        };
+    }
+
+    /// Returns `true` if the device has been in Standby mode (SBF), i.e. the last reset was a
+    /// wakeup from Standby
+    ///
+    /// The flag is kept until it is cleared with [`Pwr::clear_standby_flag`].
+    pub fn woke_from_standby(&self) -> bool {
+        // NOTE(unsafe) read-only status register
+        // NOTE the SVD swaps the field names of SR1 and SCR, this is SBF
+        unsafe { (*PWR::ptr()).sr1.read().csbf().bit_is_set() }
+    }
+
+    /// Clears the Standby flag (SBF)
+    pub fn clear_standby_flag(&mut self) {
+        // NOTE(unsafe) write-only clear register
+        // NOTE the SVD swaps the field names of SR1 and SCR, this is CSBF
+        unsafe { (*PWR::ptr()).scr.write(|w| w.sbf().set_bit()) };
+    }
+
+    /// Sets the pull of a wakeup pin held through Standby and Shutdown
+    ///
+    /// The GPIO pulls are lost in these modes, so a wakeup pin without an external pull
+    /// resistor floats and may wake the device spuriously. See [`Pwr::set_standby_pull`].
+    pub fn set_wakeup_pin_pull(&mut self, pin: WakeupPin, pull: StandbyPull) {
+        match pin {
+            WakeupPin::Pin1 => self.set_port_pull('A', 0, pull),
+            WakeupPin::Pin2 => self.set_port_pull('C', 13, pull),
+            WakeupPin::Pin3 => self.set_port_pull('E', 6, pull),
+            WakeupPin::Pin4 => self.set_port_pull('A', 2, pull),
+            WakeupPin::Pin5 => self.set_port_pull('C', 5, pull),
+        }
+    }
+
+    /// Sets the pull of `pin`, held through Standby and Shutdown
+    ///
+    /// The GPIO configuration is lost in these modes, so pins driving external circuitry
+    /// float unless a pull is applied here. This also enables applying the pull configuration
+    /// (APC), which affects the pulls set for all other pins as well.
+    pub fn set_standby_pull<MODE, HL, const P: char, const N: u8>(
+        &mut self,
+        _pin: &Pin<MODE, HL, P, N>,
+        pull: StandbyPull,
+    ) {
+        self.set_port_pull(P, N, pull);
+    }
+
+    fn set_port_pull(&mut self, port: char, pin: u8, pull: StandbyPull) {
+        // NOTE(unsafe) the pull registers are only accessed through `Pwr`
+        let pwr = unsafe { &*PWR::ptr() };
+
+        let (up, down) = match pull {
+            StandbyPull::Floating => (false, false),
+            StandbyPull::PullUp => (true, false),
+            StandbyPull::PullDown => (false, true),
+        };
+        let mask = 1 << pin;
+
+        macro_rules! set_pull {
+            ($pucr:ident, $pdcr:ident) => {{
+                pwr.$pucr.modify(|r, w| unsafe {
+                    w.bits(if up {
+                        r.bits() | mask
+                    } else {
+                        r.bits() & !mask
+                    })
+                });
+                pwr.$pdcr.modify(|r, w| unsafe {
+                    w.bits(if down {
+                        r.bits() | mask
+                    } else {
+                        r.bits() & !mask
+                    })
+                });
+            }};
+        }
+
+        match port {
+            'A' => set_pull!(pucra, pdcra),
+            'B' => set_pull!(pucrb, pdcrb),
+            'C' => set_pull!(pucrc, pdcrc),
+            'D' => set_pull!(pucrd, pdcrd),
+            'E' => set_pull!(pucre, pdcre),
+            'F' => set_pull!(pucrf, pdcrf),
+            // 'G', the last port the HAL defines pins for
+            _ => set_pull!(pucrg, pdcrg),
+        }
+
+        self.cr3.reg().modify(|_, w| w.apc().set_bit());
+    }
+
+    /// Enables the programmable voltage detector (PVD) and its interrupt on EXTI line 16
+    ///
+    /// The PVD output is set while VDD is below `level`, so `Edge::Rising` triggers the
+    /// `PVD_PVM` interrupt when VDD drops below the threshold and `Edge::Falling` when it
+    /// rises above it again. The interrupt pending bit has to be cleared with
+    /// [`Pwr::clear_pvd_interrupt`].
+    pub fn enable_pvd(&mut self, level: PvdLevel, edge: Edge, exti: &mut EXTI) {
+        self.cr2
+            .reg()
+            .modify(|_, w| unsafe { w.pls().bits(level as u8) }.pvde().set_bit());
+
+        let mask = 1 << PVD_EXTI_LINE;
+        let (rising, falling) = match edge {
+            Edge::Rising => (true, false),
+            Edge::Falling => (false, true),
+            Edge::RisingFalling => (true, true),
+        };
+        exti.rtsr1.modify(|r, w| unsafe {
+            w.bits(if rising {
+                r.bits() | mask
+            } else {
+                r.bits() & !mask
+            })
+        });
+        exti.ftsr1.modify(|r, w| unsafe {
+            w.bits(if falling {
+                r.bits() | mask
+            } else {
+                r.bits() & !mask
+            })
+        });
+        exti.imr1.modify(|r, w| unsafe { w.bits(r.bits() | mask) });
+    }
+
+    /// Disables the programmable voltage detector and masks its interrupt
+    pub fn disable_pvd(&mut self, exti: &mut EXTI) {
+        exti.imr1
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(1 << PVD_EXTI_LINE)) });
+        self.cr2.reg().modify(|_, w| w.pvde().clear_bit());
+    }
+
+    /// Marks the independent USB supply VDDUSB as valid (USV), required by the USB peripherals
+    pub fn enable_vddusb(&mut self) {
+        self.cr2.reg().modify(|_, w| w.usv().set_bit());
+    }
+
+    /// Marks the independent I/O supply VDDIO2 as valid (IOSV), required by the pins of port G
+    /// which are supplied by VDDIO2 on parts that have it
+    pub fn enable_vddio2(&mut self) {
+        self.cr2.reg().modify(|_, w| w.iosv().set_bit());
+    }
+
+    /// Returns `true` while VDD is below the threshold configured with [`Pwr::enable_pvd`]
+    pub fn pvd_is_below_threshold(&self) -> bool {
+        self.sr2().read().pvdo().bit_is_set()
+    }
+
+    /// Clears the PVD interrupt pending bit
+    ///
+    /// Unlike [`enable_pvd`](Self::enable_pvd), this does not need the `EXTI` peripheral, so it
+    /// can be called from the PVD interrupt handler.
+    pub fn clear_pvd_interrupt(&mut self) {
+        // NOTE(unsafe) PR1 is write-1-to-clear, so this atomic write only clears the PVD line
+        // and has no effect on the pending bits of the other lines, same as
+        // `ExtiPin::clear_interrupt_pending_bit`
+        unsafe { (*EXTI::ptr()).pr1.write(|w| w.bits(1 << PVD_EXTI_LINE)) };
+    }
+
+    /// Allows writes to the backup domain, i.e. the RTC, the backup registers and `RCC_BDCR`
+    pub fn disable_backup_domain_write_protection(&mut self) {
+        self.cr1.reg().modify(|_, w| w.dbp().set_bit());
+        while self.cr1.reg().read().dbp().bit_is_clear() {}
+    }
+
+    /// Write-protects the backup domain again, which is the reset state
+    pub fn enable_backup_domain_write_protection(&mut self) {
+        self.cr1.reg().modify(|_, w| w.dbp().clear_bit());
+    }
+
+    /// Allows writes to the backup domain until the returned guard is dropped
+    pub fn backup_domain_access(&mut self) -> BackupDomainAccess<'_> {
+        self.disable_backup_domain_write_protection();
+        BackupDomainAccess { pwr: self }
+    }
+
+    /// Enables charging the battery on VBAT from VDD through the internal `resistor`
+    ///
+    /// Only enable this with a rechargeable battery or super capacitor connected to VBAT.
+    /// Charging is automatically stopped while in VBAT mode.
+    pub fn enable_battery_charging(&mut self, resistor: ChargeResistor) {
+        self.cr4.reg().modify(|_, w| {
+            match resistor {
+                ChargeResistor::R5k => w.vbrs().clear_bit(),
+                ChargeResistor::R1_5k => w.vbrs().set_bit(),
+            };
+            w.vbe().set_bit()
+        });
+    }
+
+    /// Disables battery charging on VBAT
+    pub fn disable_battery_charging(&mut self) {
+        self.cr4.reg().modify(|_, w| w.vbe().clear_bit());
+    }
+
+    /// Configures whether the SRAM2 contents are retained in Standby mode (RRS)
+    ///
+    /// Retaining SRAM2 increases the Standby current consumption. Data which has to survive
+    /// Standby must be placed in SRAM2 by the linker script, as SRAM1 is always lost. SRAM2 is
+    /// never retained in Shutdown mode.
+    pub fn retain_sram2(&mut self, retain: bool) {
+        self.cr3.reg().modify(|_, w| w.rrs().bit(retain));
+    }
+
+    /// Enters Sleep mode until an interrupt occurs, all peripherals keep running
+    pub fn sleep(&mut self, scb: &mut SCB) {
+        scb.clear_sleepdeep();
+        asm::dsb();
+        asm::wfi();
+    }
+
+    /// Enters low-power run mode, with the main regulator off
+    ///
+    /// The system clock must not exceed 2 MHz in low-power run mode, e.g. MSI at 2 MHz or
+    /// less, and the voltage range can't be changed while in it.
+    pub fn enter_low_power_run(&mut self) {
+        self.cr1.reg().modify(|_, w| w.lpr().set_bit());
+    }
+
+    /// Exits low-power run mode and waits for the main regulator to be ready
+    ///
+    /// The system clock may only be raised above 2 MHz once this returned `Ok`.
+    pub fn exit_low_power_run(&mut self) -> Result<(), Error> {
+        self.cr1.reg().modify(|_, w| w.lpr().clear_bit());
+
+        for _ in 0..REGULATOR_TIMEOUT {
+            if !self.is_low_power_regulator_active() {
+                return Ok(());
+            }
+        }
+
+        Err(Error::RegulatorTimeout)
+    }
+
+    /// Enters low-power sleep mode until an interrupt occurs
+    ///
+    /// This enters low-power run mode first, see [`Pwr::enter_low_power_run`] for the
+    /// constraints on the system clock. The device stays in low-power run mode after wakeup
+    /// until [`Pwr::exit_low_power_run`] is called.
+    pub fn low_power_sleep(&mut self, scb: &mut SCB) {
+        self.enter_low_power_run();
+        self.sleep(scb);
+    }
+
+    /// Enables the internal wakeup line (EIWUL) for waking up from Standby and Shutdown
+    ///
+    /// The internal wakeup line combines the RTC alarms, the RTC wakeup timer, the RTC tamper
+    /// and timestamp events. The respective RTC interrupt has to be enabled as well, e.g.
+    /// with `Rtc::listen`, see [`Pwr::enter_standby_with_rtc_wakeup`].
+    pub fn enable_internal_wakeup(&mut self) {
+        // NOTE the SVD calls the EIWUL field EWF
+        self.cr3.reg().modify(|_, w| w.ewf().set_bit());
+    }
+
+    /// Disables the internal wakeup line
+    pub fn disable_internal_wakeup(&mut self) {
+        self.cr3.reg().modify(|_, w| w.ewf().clear_bit());
+    }
+
+    /// Enters Standby mode and wakes up after `delay` through the RTC wakeup timer
+    ///
+    /// `delay` is in seconds with the default `RtcWakeupClockSource::CkSpre`, see the
+    /// `CountDown` implementation of `rtc::WakeupTimer`. The device is reset on wakeup, so
+    /// calling this again after reset results in a periodic wakeup.
+    pub fn enter_standby_with_rtc_wakeup(
+        &mut self,
+        rtc: &mut Rtc,
+        delay: u32,
+        exti: &mut EXTI,
+        scb: &mut SCB,
+    ) -> ! {
+        rtc.listen(exti, rtc::Event::WakeupTimer);
+        // Clears the wakeup timer flag, which would otherwise keep WUFI set
+        rtc.wakeup_timer().start(delay);
+        self.enable_internal_wakeup();
+        self.enter_standby(scb)
+    }
+
+    /// Enters Stop mode and returns once the device has woken up
+    ///
+    /// `wakeup_lines` lists the EXTI lines to unmask as wakeup sources before entering Stop,
+    /// e.g. the GPIO lines 0–15, line 18 for the RTC alarms or line 20 for the RTC wakeup
+    /// timer. The trigger edges and the source itself still have to be configured, e.g. with
+    /// `ExtiPin::trigger_on_edge` or `Rtc::listen`. The lines are left unmasked after wakeup
+    /// and the corresponding interrupt handlers run before this returns.
+    ///
+    /// Note that Stop 2 can not be entered from low-power run mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of `wakeup_lines` is not a valid EXTI line, i.e. 64 or higher. No line is
+    /// unmasked in that case.
+    pub fn enter_stop(
+        &mut self,
+        mode: StopMode,
+        wakeup_lines: &[u8],
+        exti: &mut EXTI,
+        scb: &mut SCB,
+    ) {
+        assert!(wakeup_lines.iter().all(|&line| line < 64));
+
+        for &line in wakeup_lines {
+            if line < 32 {
+                exti.imr1
+                    .modify(|r, w| unsafe { w.bits(r.bits() | (1 << line)) });
+            } else {
+                exti.imr2
+                    .modify(|r, w| unsafe { w.bits(r.bits() | (1 << (line - 32))) });
+            }
+        }
+
+        self.cr1
+            .reg()
+            .modify(|_, w| unsafe { w.lpms().bits(mode as u8) });
+
+        scb.set_sleepdeep();
+        asm::dsb();
+        asm::wfi();
+        scb.clear_sleepdeep();
+    }
+
+    /// Returns `true` while the regulator is still settling after a voltage scaling (VOS)
+    /// change, the clocks must not be raised until this returns `false`
+    pub fn is_vos_changing(&self) -> bool {
+        self.sr2().read().vosf().bit_is_set()
+    }
+
+    /// Returns the current voltage scaling range
+    pub fn current_power_range(&self) -> VosRange {
+        // NOTE(unsafe) atomic read with no side effects
+        match unsafe { (*PWR::ptr()).cr1.read().vos().bits() } {
+            0b10 => VosRange::Range2,
+            _ => VosRange::Range1,
+        }
+    }
+
+    /// Sets the voltage scaling range and waits for the regulator to settle
+    ///
+    /// The clocks have to be lowered below the limit of `range` before switching to a lower
+    /// range, and may only be raised after switching to a higher range. The range can't be
+    /// changed in low-power run mode.
+    pub fn set_power_range(&mut self, range: VosRange) {
+        self.cr1
+            .reg()
+            .modify(|_, w| unsafe { w.vos().bits(range as u8) });
+        self.wait_vos_ready();
+    }
+
+    /// Enables or disables the Range 1 boost mode (R1MODE), required to run the system clock
+    /// above 80 MHz on L4+ parts
+    ///
+    /// Only called by `CFGR::freeze` while the system clock is low, which also sets up the
+    /// flash wait states for the boost mode, see [`CFGR::boost`](crate::rcc::CFGR::boost).
+    /// Panics if boost mode is enabled while the regulator is not in voltage range 1.
+    #[cfg(any(
+        // feature = "stm32l4p5",
+        // feature = "stm32l4q5",
+        // feature = "stm32l4r5",
+        // feature = "stm32l4s5",
+        // feature = "stm32l4r7",
+        // feature = "stm32l4s7",
+        feature = "stm32l4r9",
+        feature = "stm32l4s9",
+    ))]
+    pub(crate) fn set_boost_mode(&mut self, boost: bool) {
+        assert!(!boost || self.current_power_range() == VosRange::Range1);
+
+        // NOTE(unsafe) CR5 is only accessed through `Pwr`
+        // NOTE(read_volatile/write_volatile) the PAC is missing CR5, at offset 0x80
+        unsafe {
+            let cr5 = (PWR::ptr() as *mut u8).add(0x80) as *mut u32;
+            // R1MODE is bit 8, cleared for boost mode
+            let bits = ptr::read_volatile(cr5);
+            ptr::write_volatile(
+                cr5,
+                if boost {
+                    bits & !(1 << 8)
+                } else {
+                    bits | (1 << 8)
+                },
+            );
+        }
+        self.wait_vos_ready();
+    }
+
+    /// Waits until the regulator has settled after a voltage scaling range change
+    pub fn wait_vos_ready(&self) {
+        while self.is_vos_changing() {}
+    }
+
+    /// Returns `true` if the regulator is running in low-power mode (REGLPF)
+    pub fn is_low_power_regulator_active(&self) -> bool {
+        self.sr2().read().reglpf().bit_is_set()
+    }
+
+    /// Returns `true` if the low-power regulator is ready (REGLPS)
+    pub fn is_low_power_regulator_ready(&self) -> bool {
+        self.sr2().read().reglps().bit_is_set()
+    }
+
+    /// Enters Standby mode, the device is reset on wakeup
+    ///
+    /// See [`Pwr::enter_shutdown`] for how wakeup flags are handled.
+    pub fn enter_standby(&mut self, scb: &mut SCB) -> ! {
+        // 0b011: Standby mode
+        self.enter_reset_mode(0b011, scb)
+    }
+
+    /// Enters Shutdown mode, the device is reset on wakeup
+    ///
+    /// The wakeup pin flags and the Standby flag are cleared right before entering the low-power
+    /// mode, as the device would wake up immediately with a wakeup flag still set. A wakeup
+    /// event occurring after the flags are cleared still wakes the device as intended. The
+    /// internal wakeup flag (WUFI) can't be cleared here, it is only cleared once the flags of
+    /// all internal wakeup sources, e.g. the RTC, have been cleared, which has to be done
+    /// before calling this.
+    pub fn enter_shutdown(&mut self, scb: &mut SCB) -> ! {
+        // 0b100: Shutdown mode
+        self.enter_reset_mode(0b100, scb)
+    }
+
+    fn enter_reset_mode(&mut self, lpms: u8, scb: &mut SCB) -> ! {
+        self.cr1.reg().modify(|_, w| unsafe { w.lpms().bits(lpms) });
+        scb.set_sleepdeep();
+
+        self.clear_wakeup_flags();
+        self.clear_standby_flag();
+
+        asm::dsb();
+        loop {
+            asm::wfi();
+        }
+    }
+
+    fn sr2(&self) -> &pwr::SR2 {
+        // NOTE(unsafe) read-only status register
+        unsafe { &(*PWR::ptr()).sr2 }
+    }
+}
+
+/// Write access to the backup domain, see [`Pwr::backup_domain_access`]
+///
+/// The backup domain is write-protected again when this is dropped.
+pub struct BackupDomainAccess<'a> {
+    pwr: &'a mut Pwr,
+}
+
+impl Drop for BackupDomainAccess<'_> {
+    fn drop(&mut self) {
+        self.pwr.enable_backup_domain_write_protection();
+    }
+}
+
 /// CR1
 pub struct CR1 {
     _0: (),
 }
 
 impl CR1 {
-    // TODO remove `allow`
-    #[allow(dead_code)]
     pub(crate) fn reg(&mut self) -> &pwr::CR1 {
         // NOTE(unsafe) this proxy grants exclusive access to this register
         unsafe { &(*PWR::ptr()).cr1 }
@@ -48,8 +692,6 @@ pub struct CR2 {
 }
 
 impl CR2 {
-    // TODO remove `allow`
-    #[allow(dead_code)]
     pub(crate) fn reg(&mut self) -> &pwr::CR2 {
         // NOTE(unsafe) this proxy grants exclusive access to this register
         unsafe { &(*PWR::ptr()).cr2 }
@@ -61,8 +703,6 @@ pub struct CR3 {
 }
 
 impl CR3 {
-    // TODO remove `allow`
-    #[allow(dead_code)]
     pub(crate) fn reg(&mut self) -> &pwr::CR3 {
         // NOTE(unsafe) this proxy grants exclusive access to this register
         unsafe { &(*PWR::ptr()).cr3 }
@@ -74,8 +714,6 @@ pub struct CR4 {
 }
 
 impl CR4 {
-    // TODO remove `allow`
-    #[allow(dead_code)]
     pub(crate) fn reg(&mut self) -> &pwr::CR4 {
         // NOTE(unsafe) this proxy grants exclusive access to this register
         unsafe { &(*PWR::ptr()).cr4 }
@@ -4,6 +4,7 @@ use crate::stm32::{rcc, RCC};
 use cast::u32;
 
 use crate::flash::ACR;
+use crate::gpio::{Afr, Alternate, PushPull, H8, MODER, OTYPER, PA8};
 use crate::pwr::Pwr;
 use crate::time::Hertz;
 use fugit::RateExtU32;
@@ -77,10 +78,13 @@ impl RccExt for RCC {
             csr: CSR { _0: () },
             crrcr: CRRCR { _0: () },
             ccipr: CCIPR { _0: () },
+            mco: MCO { _0: () },
             cfgr: CFGR {
                 hse: None,
                 lse: None,
+                lse_drive: LseDrive::High,
                 msi: None,
+                msi_pll_mode: true,
                 hsi48: false,
                 lsi: false,
                 hclk: None,
@@ -89,6 +93,8 @@ impl RccExt for RCC {
                 sysclk: None,
                 pll_source: None,
                 pll_config: None,
+                pllsai1_config: None,
+                boost: false,
             },
         }
     }
@@ -118,6 +124,75 @@ pub struct Rcc {
     pub crrcr: CRRCR,
     /// Peripherals independent clock configuration register
     pub ccipr: CCIPR,
+    /// Microcontroller clock output
+    pub mco: MCO,
+}
+
+/// Clock sources which can be output on MCO
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum McoSource {
+    /// System clock
+    SYSCLK = 0b0001,
+    /// Multi-speed internal clock
+    MSI = 0b0010,
+    /// High-speed internal clock
+    HSI16 = 0b0011,
+    /// High-speed external clock
+    HSE = 0b0100,
+    /// Main PLL system clock output
+    PLL = 0b0101,
+    /// Low-speed internal clock
+    LSI = 0b0110,
+    /// Low-speed external clock
+    LSE = 0b0111,
+    /// 48 MHz internal clock, not available on all stm32l4x6 series
+    HSI48 = 0b1000,
+}
+
+/// MCO prescaler
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum McoPrescaler {
+    Div1 = 0b000,
+    Div2 = 0b001,
+    Div4 = 0b010,
+    Div8 = 0b011,
+    Div16 = 0b100,
+}
+
+/// Microcontroller clock output (MCO), configured through the MCOSEL and MCOPRE fields of the
+/// clock configuration register
+pub struct MCO {
+    _0: (),
+}
+
+impl MCO {
+    /// Outputs `source` divided by `prescaler` on the MCO pin PA8
+    ///
+    /// Has to be called after [`CFGR::freeze`], which may reset the MCO configuration.
+    pub fn enable<MODE>(
+        &mut self,
+        source: McoSource,
+        prescaler: McoPrescaler,
+        pin: PA8<MODE>,
+        moder: &mut MODER<'A'>,
+        otyper: &mut OTYPER<'A'>,
+        afrh: &mut Afr<H8, 'A'>,
+    ) -> PA8<Alternate<PushPull, 0>> {
+        // NOTE(unsafe) this proxy grants exclusive access to MCOSEL and MCOPRE, which are only
+        // reset by `CFGR::freeze`
+        let rcc = unsafe { &*RCC::ptr() };
+
+        // NOTE the PAC is missing the 4th MCOSEL bit and write access to MCOPRE
+        rcc.cfgr.modify(|r, w| unsafe {
+            w.bits(
+                (r.bits() & !(0b111_1111 << 24))
+                    | ((prescaler as u32) << 28)
+                    | ((source as u32) << 24),
+            )
+        });
+
+        pin.into_alternate(moder, otyper, afrh)
+    }
 }
 
 /// CSR Control/Status Register
@@ -126,12 +201,70 @@ pub struct CSR {
 }
 
 impl CSR {
-    // TODO remove `allow`
-    #[allow(dead_code)]
     pub(crate) fn csr(&mut self) -> &rcc::CSR {
         // NOTE(unsafe) this proxy grants exclusive access to this register
         unsafe { &(*RCC::ptr()).csr }
     }
+
+    /// Returns the cause of the last reset, as recorded in the reset flags
+    ///
+    /// The flags accumulate across resets until they are cleared with
+    /// [`clear_reset_flags`](Self::clear_reset_flags), in which case the most specific cause
+    /// is returned. Note that the pin reset flag is set on every reset, as the internal reset
+    /// sources also drive the NRST pin low.
+    pub fn reset_cause(&mut self) -> ResetCause {
+        let csr = self.csr().read();
+
+        // NOTE the PAC calls LPWRRSTF `lpwrstf`
+        if csr.lpwrstf().bit_is_set() {
+            ResetCause::LowPower
+        } else if csr.wwdgrstf().bit_is_set() {
+            ResetCause::WindowWatchdog
+        } else if csr.iwdgrstf().bit_is_set() {
+            ResetCause::IndependentWatchdog
+        } else if csr.sftrstf().bit_is_set() {
+            ResetCause::Software
+        } else if csr.bits() & (1 << 24) != 0 {
+            // NOTE FIREWALLRSTF, bit 24, is missing in some PACs
+            ResetCause::Firewall
+        } else if csr.oblrstf().bit_is_set() {
+            ResetCause::OptionByteLoader
+        } else if csr.borrstf().bit_is_set() {
+            ResetCause::BrownOut
+        } else if csr.pinrstf().bit_is_set() {
+            ResetCause::Pin
+        } else {
+            ResetCause::Unknown
+        }
+    }
+
+    /// Clears all reset flags (RMVF)
+    pub fn clear_reset_flags(&mut self) {
+        self.csr().modify(|_, w| w.rmvf().set_bit());
+    }
+}
+
+/// Cause of the last reset, see [`CSR::reset_cause`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ResetCause {
+    /// Illegal Stop, Standby or Shutdown mode entry (LPWRRSTF)
+    LowPower,
+    /// Window watchdog (WWDGRSTF)
+    WindowWatchdog,
+    /// Independent watchdog (IWDGRSTF)
+    IndependentWatchdog,
+    /// Software reset, e.g. `SCB::sys_reset` (SFTRSTF)
+    Software,
+    /// Firewall (FIREWALLRSTF)
+    Firewall,
+    /// Option byte loading (OBLRSTF)
+    OptionByteLoader,
+    /// Brown-out reset, which includes power-on (BORRSTF)
+    BrownOut,
+    /// External reset on the NRST pin (PINRSTF)
+    Pin,
+    /// No reset flag is set, e.g. after they have been cleared
+    Unknown,
 }
 
 /// Clock recovery RC register
@@ -215,6 +348,18 @@ macro_rules! bus_struct {
                     // NOTE(unsafe) this proxy grants exclusive access to this register
                     unsafe { &(*RCC::ptr()).$rst }
                 }
+
+                /// Resets peripheral `P` on this bus by pulsing its reset bit, e.g. to recover
+                /// an I2C stuck after a bus fault with `rcc.apb1r1.reset_peripheral::<I2C1>()`
+                ///
+                /// All registers of the peripheral return to their reset values, so it has to be
+                /// configured again afterwards.
+                pub fn reset_peripheral<P>(&mut self)
+                where
+                    P: Reset<Bus = Self>,
+                {
+                    P::reset(self);
+                }
             }
         )+
     };
@@ -318,6 +463,22 @@ struct LseConfig {
     css: ClockSecuritySystem,
 }
 
+/// LSE oscillator drive capability
+///
+/// Crystals with a higher critical transconductance need a higher drive to start reliably, at
+/// the cost of a higher consumption.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LseDrive {
+    /// Lower driving capability
+    Low = 0b00,
+    /// Medium low driving capability
+    MediumLow = 0b01,
+    /// Medium high driving capability
+    MediumHigh = 0b10,
+    /// Higher driving capability
+    High = 0b11,
+}
+
 /// Crystal bypass selector
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum CrystalBypass {
@@ -342,11 +503,16 @@ pub enum ClockSecuritySystem {
 
 const HSI: u32 = 16_000_000; // Hz
 
+/// Maximum VCO output frequency of the PLLs
+const PLL_VCO_MAX: u32 = 344_000_000; // Hz
+
 /// Clock configuration
 pub struct CFGR {
     hse: Option<HseConfig>,
     lse: Option<LseConfig>,
+    lse_drive: LseDrive,
     msi: Option<MsiFreq>,
+    msi_pll_mode: bool,
     hsi48: bool,
     lsi: bool,
     hclk: Option<u32>,
@@ -355,6 +521,8 @@ pub struct CFGR {
     sysclk: Option<u32>,
     pll_source: Option<PllSource>,
     pll_config: Option<PllConfig>,
+    pllsai1_config: Option<PllSai1Config>,
+    boost: bool,
 }
 
 impl CFGR {
@@ -376,6 +544,14 @@ impl CFGR {
         self
     }
 
+    /// Sets the drive capability of the LSE oscillator, the default is `LseDrive::High`
+    ///
+    /// Only used when the LSE is enabled with a crystal, i.e. not bypassed.
+    pub fn lse_drive(mut self, drive: LseDrive) -> Self {
+        self.lse_drive = drive;
+        self
+    }
+
     /// Sets a frequency for the AHB bus
     pub fn hclk(mut self, freq: Hertz) -> Self {
         self.hclk = Some(freq.raw());
@@ -383,17 +559,31 @@ impl CFGR {
     }
 
     /// Enable the 48 MHz USB, RNG, SDMMC HSI clock source. Not available on all stm32l4x6 series
+    ///
+    /// The HSI48 is selected as the 48 MHz clock (CLK48) unless the MSI runs at 48 MHz or the
+    /// Q output of the [`pllsai1`](CFGR::pllsai1) is enabled. Use the [`crs`](crate::crs)
+    /// module to trim it against the USB start-of-frame.
     pub fn hsi48(mut self, on: bool) -> Self {
         self.hsi48 = on;
         self
     }
 
     /// Enables the MSI with the specified speed
+    ///
+    /// The MSI is used as the system clock unless a [`sysclk`](Self::sysclk) is set, which
+    /// is generated with the PLL instead.
     pub fn msi(mut self, range: MsiFreq) -> Self {
         self.msi = Some(range);
         self
     }
 
+    /// Sets whether the MSI is automatically trimmed against the LSE (MSIPLLEN) when both are
+    /// enabled, the default is on
+    pub fn msi_pll_mode(mut self, on: bool) -> Self {
+        self.msi_pll_mode = on;
+        self
+    }
+
     /// Sets LSI clock on (the default) or off
     pub fn lsi(mut self, on: bool) -> Self {
         self.lsi = on;
@@ -431,6 +621,35 @@ impl CFGR {
         self
     }
 
+    /// Enables the PLLSAI1 with the given configuration
+    ///
+    /// The PLLSAI1 shares the source and input divider of the main PLL. If enabled, its Q output
+    /// is selected as the 48 MHz clock (CLK48) for USB, RNG and SDMMC, and its R output as the
+    /// ADC clock.
+    pub fn pllsai1(mut self, cfg: PllSai1Config) -> Self {
+        self.pllsai1_config = Some(cfg);
+        self
+    }
+
+    /// Enables the Range 1 boost mode, allowing a system clock of up to 120 MHz
+    ///
+    /// The regulator has to be in voltage range 1. The boost mode and the flash wait states are
+    /// set up together when freezing the configuration.
+    #[cfg(any(
+        // feature = "stm32l4p5",
+        // feature = "stm32l4q5",
+        // feature = "stm32l4r5",
+        // feature = "stm32l4s5",
+        // feature = "stm32l4r7",
+        // feature = "stm32l4s7",
+        feature = "stm32l4r9",
+        feature = "stm32l4s9",
+    ))]
+    pub fn boost(mut self, on: bool) -> Self {
+        self.boost = on;
+        self
+    }
+
     /// Freezes the clock configuration, making it effective
     pub fn freeze(&self, acr: &mut ACR, pwr: &mut Pwr) -> Clocks {
         let rcc = unsafe { &*RCC::ptr() };
@@ -455,6 +674,19 @@ impl CFGR {
             while rcc.cfgr.read().sws().bits() != 0 {}
         }
 
+        // The system clock is now at most 48 MHz, so the boost mode can be switched either way
+        #[cfg(any(
+            // feature = "stm32l4p5",
+            // feature = "stm32l4q5",
+            // feature = "stm32l4r5",
+            // feature = "stm32l4s5",
+            // feature = "stm32l4r7",
+            // feature = "stm32l4s7",
+            feature = "stm32l4r9",
+            feature = "stm32l4s9",
+        ))]
+        pwr.set_boost_mode(self.boost);
+
         //
         // 1. Setup clocks
         //
@@ -491,8 +723,8 @@ impl CFGR {
                     w.lsebyp().set_bit();
                 } else {
                     unsafe {
-                        w.lsedrv().bits(0b11);
-                    } // Max drive strength, TODO: should probably be settable
+                        w.lsedrv().bits(self.lse_drive as u8);
+                    }
                 }
 
                 w
@@ -541,7 +773,7 @@ impl CFGR {
                         .set_bit();
 
                     // If LSE is enabled, enable calibration of MSI
-                    if self.lse.is_some() {
+                    if self.lse.is_some() && self.msi_pll_mode {
                         w.msipllen().set_bit();
                     }
 
@@ -560,6 +792,11 @@ impl CFGR {
 
             // Wait until HSI48 is running
             while rcc.crrcr.read().hsi48rdy().bit_is_clear() {}
+
+            // Select HSI48 as clock source for usb48, rng ... This is the reset value, but
+            // restore it in case a bootloader or a previous configuration selected another
+            // source.
+            unsafe { rcc.ccipr.modify(|_, w| w.clk48sel().bits(0b00)) };
         }
 
         // Select MSI as clock source for usb48, rng ...
@@ -632,7 +869,8 @@ impl CFGR {
             (None, None) => MsiFreq::RANGE4M.to_hertz().raw(),
         };
 
-        assert!(sysclk <= 80_000_000);
+        let max_sysclk = if self.boost { 120_000_000 } else { 80_000_000 };
+        assert!(sysclk <= max_sysclk);
 
         let (hpre_bits, hpre_div) = self
             .hclk
@@ -692,7 +930,10 @@ impl CFGR {
         // adjust flash wait states
         unsafe {
             acr.acr().write(|w| {
-                w.latency().bits(if hclk <= 16_000_000 {
+                w.latency().bits(if self.boost {
+                    // Range 1 boost mode, 20 MHz per wait state
+                    ((hclk - 1) / 20_000_000) as u8
+                } else if hclk <= 16_000_000 {
                     0b000
                 } else if hclk <= 32_000_000 {
                     0b001
@@ -721,8 +962,8 @@ impl CFGR {
             assert!(clock_speed >= 4_000_000); // VCO input clock min
             assert!(clock_speed <= 16_000_000); // VCO input clock max
             assert!(vco >= 64_000_000); // VCO output min
-            assert!(vco <= 334_000_000); // VCO output max
-            assert!(output_clock <= 80_000_000); // Max output clock
+            assert!(vco <= PLL_VCO_MAX); // VCO output max
+            assert!(output_clock <= max_sysclk); // Max output clock
 
             // use PLL as source
             sysclk_src_bits = 0b11;
@@ -748,6 +989,10 @@ impl CFGR {
 
             rcc.pllcfgr.modify(|_, w| w.pllren().set_bit());
 
+            // Above 80 MHz, the AHB clock has to be divided by 2 for at least 1 µs while
+            // switching to the PLL
+            let transition = sysclk > 80_000_000 && hpre_div == 1;
+
             // SW: PLL selected as system clock
             rcc.cfgr.modify(|_, w| unsafe {
                 w.ppre2()
@@ -755,10 +1000,16 @@ impl CFGR {
                     .ppre1()
                     .bits(ppre1_bits)
                     .hpre()
-                    .bits(hpre_bits)
+                    .bits(if transition { 0b1000 } else { hpre_bits })
                     .sw()
                     .bits(sysclk_src_bits)
             });
+
+            if transition {
+                while rcc.cfgr.read().sws().bits() != sysclk_src_bits {}
+                cortex_m::asm::delay(sysclk / 1_000_000);
+                rcc.cfgr.modify(|_, w| unsafe { w.hpre().bits(hpre_bits) });
+            }
         } else {
             // use MSI as fallback source for sysclk
             sysclk_src_bits = 0b00;
@@ -781,6 +1032,103 @@ impl CFGR {
 
         while rcc.cfgr.read().sws().bits() != sysclk_src_bits {}
 
+        //
+        // 2b. Setup PLLSAI1
+        //
+
+        let mut clk48 = if self.hsi48 || msi == Some(MsiFreq::RANGE48M) {
+            Some(48.MHz())
+        } else {
+            None
+        };
+        let mut sai1clk = None;
+        let mut adcclk = None;
+
+        if let Some(cfg) = self.pllsai1_config {
+            let m = if let Some(pllconf) = pllconf {
+                pllconf.m
+            } else {
+                // The main PLL is unused, only set up its source and input divider. Use the
+                // smallest division factor which brings the VCO input down to 16 MHz.
+                let m = clock_speed.div_ceil(16_000_000).max(1) - 1;
+                assert!(m <= 7);
+                let m = m as u8;
+                rcc.pllcfgr.modify(|_, w| unsafe {
+                    w.pllsrc().bits(pll_source.to_pllsrc()).pllm().bits(m)
+                });
+                m
+            };
+            let input = clock_speed / (m as u32 + 1);
+            let vco = input * cfg.n as u32;
+
+            assert!(cfg.n >= 8); // Allowed min multiplier
+            assert!(cfg.n <= 86); // Allowed max multiplier
+            assert!(input >= 4_000_000); // VCO input clock min
+            assert!(input <= 16_000_000); // VCO input clock max
+            assert!(vco >= 64_000_000); // VCO output min
+            assert!(vco <= PLL_VCO_MAX); // VCO output max
+
+            rcc.cr.modify(|_, w| w.pllsai1on().clear_bit());
+            while rcc.cr.read().pllsai1rdy().bit_is_set() {}
+
+            rcc.pllsai1cfgr.modify(|_, w| unsafe {
+                #[cfg(any(
+                    // feature = "stm32l4p5",
+                    // feature = "stm32l4q5",
+                    // feature = "stm32l4r5",
+                    // feature = "stm32l4s5",
+                    // feature = "stm32l4r7",
+                    // feature = "stm32l4s7",
+                    feature = "stm32l4r9",
+                    feature = "stm32l4s9",
+                ))]
+                w.pllsai1m().bits(m);
+
+                w.pllsai1n().bits(cfg.n);
+                if let Some(p) = cfg.p {
+                    w.pllsai1p().bit(p == PllSai1PDivider::Div17);
+                }
+                if let Some(q) = cfg.q {
+                    w.pllsai1q().bits(q.to_bits());
+                }
+                if let Some(r) = cfg.r {
+                    w.pllsai1r().bits(r.to_bits());
+                }
+
+                w.pllsai1pen()
+                    .bit(cfg.p.is_some())
+                    .pllsai1qen()
+                    .bit(cfg.q.is_some())
+                    .pllsai1ren()
+                    .bit(cfg.r.is_some())
+            });
+
+            rcc.cr.modify(|_, w| w.pllsai1on().set_bit());
+            while rcc.cr.read().pllsai1rdy().bit_is_clear() {}
+
+            if let Some(p) = cfg.p {
+                sai1clk = Some((vco / p.to_division_factor()).Hz());
+            }
+
+            if let Some(q) = cfg.q {
+                let q = vco / q.to_division_factor();
+                assert!(q <= 80_000_000);
+
+                // Select PLLSAI1 Q as clock source for usb48, rng ...
+                unsafe { rcc.ccipr.modify(|_, w| w.clk48sel().bits(0b01)) };
+                clk48 = Some(q.Hz());
+            }
+
+            if let Some(r) = cfg.r {
+                let r = vco / r.to_division_factor();
+                assert!(r <= 80_000_000);
+
+                // Select PLLSAI1 R as clock source for the ADC
+                unsafe { rcc.ccipr.modify(|_, w| w.adcsel().bits(0b01)) };
+                adcclk = Some(r.Hz());
+            }
+        }
+
         //
         // 3. Shutdown unused clocks that have auto-started
         //
@@ -807,6 +1155,9 @@ impl CFGR {
             ppre2,
             sysclk: sysclk.Hz(),
             pll_source: pllconf.map(|_| pll_source),
+            clk48,
+            sai1clk,
+            adcclk,
         }
     }
 }
@@ -867,6 +1218,71 @@ impl PllConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// PLLSAI1 P output divider options
+pub enum PllSai1PDivider {
+    /// Divide PLLSAI1 output by 7
+    Div7,
+    /// Divide PLLSAI1 output by 17
+    Div17,
+}
+
+impl PllSai1PDivider {
+    #[inline(always)]
+    fn to_division_factor(self) -> u32 {
+        match self {
+            Self::Div7 => 7,
+            Self::Div17 => 17,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+/// PLLSAI1 Configuration
+pub struct PllSai1Config {
+    // PLLSAI1 multiplication factor
+    n: u8,
+    // PLLSAI1 division factor for the SAI1 clock
+    p: Option<PllSai1PDivider>,
+    // PLLSAI1 division factor for the 48 MHz clock
+    q: Option<PllDivider>,
+    // PLLSAI1 division factor for the ADC clock
+    r: Option<PllDivider>,
+}
+
+impl PllSai1Config {
+    /// Create a new PLLSAI1 config with all outputs disabled
+    ///
+    /// PLLSAI1 output = ((SourceClk / input_divider) * multiplier) / output_divider, the
+    /// source and input divider are the ones of the main PLL.
+    pub fn new(multiplier: u8) -> Self {
+        PllSai1Config {
+            n: multiplier,
+            p: None,
+            q: None,
+            r: None,
+        }
+    }
+
+    /// Enables the P output, used as the SAI1 clock
+    pub fn p(mut self, divider: PllSai1PDivider) -> Self {
+        self.p = Some(divider);
+        self
+    }
+
+    /// Enables the Q output, used as the 48 MHz clock for USB, RNG and SDMMC
+    pub fn q(mut self, divider: PllDivider) -> Self {
+        self.q = Some(divider);
+        self
+    }
+
+    /// Enables the R output, used as the ADC clock
+    pub fn r(mut self, divider: PllDivider) -> Self {
+        self.r = Some(divider);
+        self
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 /// PLL Source
 pub enum PllSource {
@@ -904,6 +1320,9 @@ pub struct Clocks {
     ppre2: u8,
     sysclk: Hertz,
     pll_source: Option<PllSource>,
+    clk48: Option<Hertz>,
+    sai1clk: Option<Hertz>,
+    adcclk: Option<Hertz>,
 }
 
 impl Clocks {
@@ -942,18 +1361,51 @@ impl Clocks {
         self.pclk2
     }
 
+    /// Returns the kernel clock frequency of the timers on APB1
+    ///
+    /// This is twice the APB1 frequency if the APB1 prescaler is not 1.
+    pub fn timclk1(&self) -> Hertz {
+        if self.ppre1() == 1 {
+            self.pclk1
+        } else {
+            self.pclk1 * 2
+        }
+    }
+
+    /// Returns the kernel clock frequency of the timers on APB2
+    ///
+    /// This is twice the APB2 frequency if the APB2 prescaler is not 1.
+    pub fn timclk2(&self) -> Hertz {
+        if self.ppre2() == 1 {
+            self.pclk2
+        } else {
+            self.pclk2 * 2
+        }
+    }
+
     /// Get which source is being used for PLL
     pub fn pll_source(&self) -> Option<PllSource> {
         self.pll_source
     }
 
-    // TODO remove `allow`
-    #[allow(dead_code)]
+    /// Returns the frequency of the 48 MHz clock (CLK48) used by USB, RNG and SDMMC, if any
+    pub fn clk48(&self) -> Option<Hertz> {
+        self.clk48
+    }
+
+    /// Returns the frequency of the SAI1 clock generated by the PLLSAI1, if enabled
+    pub fn sai1clk(&self) -> Option<Hertz> {
+        self.sai1clk
+    }
+
+    /// Returns the frequency of the ADC clock generated by the PLLSAI1, if enabled
+    pub fn adcclk(&self) -> Option<Hertz> {
+        self.adcclk
+    }
+
     pub(crate) fn ppre1(&self) -> u8 {
         self.ppre1
     }
-    // TODO remove `allow`
-    #[allow(dead_code)]
     pub(crate) fn ppre2(&self) -> u8 {
         self.ppre2
     }
@@ -7,14 +7,14 @@ use core::marker::PhantomData;
 use core::ops::DerefMut;
 use core::ptr;
 use core::sync::atomic::{self, Ordering};
-use embedded_dma::StaticWriteBuffer;
+use embedded_dma::{StaticReadBuffer, StaticWriteBuffer};
 use stable_deref_trait::StableDeref;
 
 use crate::hal::serial::{self, Write};
 
 use crate::dma::{
-    dma1, CircBuffer, DMAFrame, FrameReader, FrameSender, Receive, RxDma, TransferPayload,
-    Transmit, TxDma,
+    dma1, CircBuffer, CircReadDma, DMAFrame, FrameReader, FrameSender, Receive, RxDma, Transfer,
+    TransferPayload, Transmit, TxDma, R, W,
 };
 use crate::dmamux::{DmaInput, DmaMux};
 use crate::gpio::{self, Alternate, OpenDrain, PushPull};
@@ -82,6 +82,20 @@ pub enum Parity {
     ParityOdd,
 }
 
+/// USART word length settings, excluding the parity bit
+pub enum WordLength {
+    /// 7 data bits
+    DataBits7,
+    /// 8 data bits
+    DataBits8,
+    /// 9 data bits
+    ///
+    /// The `serial::Read<u8>` impl truncates received words to their lower 8 bits and the
+    /// `serial::Write<u8>` impl always sends the 9th bit as 0. Use `Rx::read_u16` and
+    /// `Tx::write_u16` to access the 9th bit.
+    DataBits9,
+}
+
 /// USART stopbits settings
 pub enum StopBits {
     /// 1 stop bit
@@ -94,6 +108,14 @@ pub enum StopBits {
     STOP1P5,
 }
 
+/// RS485 driver enable polarity
+pub enum DriverEnablePolarity {
+    /// DE is active high
+    ActiveHigh,
+    /// DE is active low
+    ActiveLow,
+}
+
 /// USART oversampling settings
 pub enum Oversampling {
     /// Oversample 8 times (allows for faster data rates)
@@ -102,16 +124,66 @@ pub enum Oversampling {
     Over16,
 }
 
+/// USART FIFO threshold, as a fraction of the 8 byte FIFO
+#[cfg(any(
+    // feature = "stm32l4p5",
+    // feature = "stm32l4q5",
+    // feature = "stm32l4r5",
+    // feature = "stm32l4s5",
+    // feature = "stm32l4r7",
+    // feature = "stm32l4s7",
+    feature = "stm32l4r9",
+    feature = "stm32l4s9",
+))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FifoThreshold {
+    /// 1/8 of the FIFO
+    Eighth = 0b000,
+    /// 1/4 of the FIFO
+    Quarter = 0b001,
+    /// 1/2 of the FIFO
+    Half = 0b010,
+    /// 3/4 of the FIFO
+    ThreeQuarters = 0b011,
+    /// 7/8 of the FIFO
+    SevenEighths = 0b100,
+    /// Full (RX) or empty (TX) FIFO
+    Full = 0b101,
+}
+
 /// USART Configuration structure
 pub struct Config {
     baudrate: Bps,
     parity: Parity,
+    word_length: WordLength,
     stopbits: StopBits,
     oversampling: Oversampling,
     character_match: Option<u8>,
     receiver_timeout: Option<u32>,
     disable_overrun: bool,
     onebit_sampling: bool,
+    smartcard: Option<Smartcard>,
+    de_polarity: DriverEnablePolarity,
+    de_assertion_time: u8,
+    de_deassertion_time: u8,
+    #[cfg(any(
+        // feature = "stm32l4p5",
+        // feature = "stm32l4q5",
+        // feature = "stm32l4r5",
+        // feature = "stm32l4s5",
+        // feature = "stm32l4r7",
+        // feature = "stm32l4s7",
+        feature = "stm32l4r9",
+        feature = "stm32l4s9",
+    ))]
+    fifo: Option<(FifoThreshold, FifoThreshold)>,
+}
+
+/// ISO 7816 smartcard mode settings
+struct Smartcard {
+    guard_time: u8,
+    clock_prescaler: u8,
+    retries: u8,
 }
 
 impl Config {
@@ -128,17 +200,37 @@ impl Config {
     }
 
     /// Set parity to even
+    ///
+    /// Note that parity can not be combined with 9 data bits, doing so will cause a panic.
     pub fn parity_even(mut self) -> Self {
+        assert!(!matches!(self.word_length, WordLength::DataBits9));
         self.parity = Parity::ParityEven;
         self
     }
 
     /// Set parity to odd
+    ///
+    /// Note that parity can not be combined with 9 data bits, doing so will cause a panic.
     pub fn parity_odd(mut self) -> Self {
+        assert!(!matches!(self.word_length, WordLength::DataBits9));
         self.parity = Parity::ParityOdd;
         self
     }
 
+    /// Set the number of data bits
+    ///
+    /// With 9 data bits, use `Rx::read_u16` and `Tx::write_u16` to access the full words.
+    ///
+    /// Note that 9 data bits can not be combined with parity, doing so will cause a panic.
+    pub fn word_length(mut self, word_length: WordLength) -> Self {
+        assert!(
+            !matches!(word_length, WordLength::DataBits9)
+                || matches!(self.parity, Parity::ParityNone)
+        );
+        self.word_length = word_length;
+        self
+    }
+
     /// Set the number of stopbits
     pub fn stopbits(mut self, stopbits: StopBits) -> Self {
         self.stopbits = stopbits;
@@ -177,6 +269,80 @@ impl Config {
         self.onebit_sampling = true;
         self
     }
+
+    /// Enable ISO 7816 smartcard mode
+    ///
+    /// The card clock is output on the CK pin, which has to be configured in its alternate
+    /// function mode by the user, at `pclk / (2 * clock_prescaler)`. `guard_time` is the number
+    /// of baud clocks inserted after each transmitted character and `retries` is the number of
+    /// times a character is repeated when the card signals a parity error (0 disables
+    /// repetition). A NACK is sent on received parity errors.
+    ///
+    /// This also selects even parity and 1.5 stop bits as required by the standard. Smartcard
+    /// mode is only supported by USART1, USART2 and USART3 and can't be used with half-duplex
+    /// pins, creating the serial panics otherwise.
+    ///
+    /// Note that `clock_prescaler` takes 5 bits and `retries` takes 3 bits, using more than
+    /// this, a `clock_prescaler` of 0 or 9 data bits will cause a panic.
+    pub fn smartcard(mut self, guard_time: u8, clock_prescaler: u8, retries: u8) -> Self {
+        assert!(clock_prescaler > 0 && clock_prescaler < 1 << 5);
+        assert!(retries < 1 << 3);
+        self = self.parity_even();
+        self.stopbits = StopBits::STOP1P5;
+        self.smartcard = Some(Smartcard {
+            guard_time,
+            clock_prescaler,
+            retries,
+        });
+        self
+    }
+
+    /// Set the polarity of the RS485 driver enable signal
+    ///
+    /// Only used when the serial port is constructed with a DE pin.
+    pub fn driver_enable(mut self, polarity: DriverEnablePolarity) -> Self {
+        self.de_polarity = polarity;
+        self
+    }
+
+    /// Set the time between DE activation and the beginning of the start bit, in sample time
+    /// units (1/8 or 1/16 bit duration, depending on the oversampling)
+    ///
+    /// Note that it only takes 5 bits, using more than this will cause a panic.
+    pub fn assertion_time(mut self, assertion_time: u8) -> Self {
+        assert!(assertion_time < 1 << 5);
+        self.de_assertion_time = assertion_time;
+        self
+    }
+
+    /// Set the time between the end of the last stop bit and DE deactivation, in sample time
+    /// units (1/8 or 1/16 bit duration, depending on the oversampling)
+    ///
+    /// Note that it only takes 5 bits, using more than this will cause a panic.
+    pub fn deassertion_time(mut self, deassertion_time: u8) -> Self {
+        assert!(deassertion_time < 1 << 5);
+        self.de_deassertion_time = deassertion_time;
+        self
+    }
+
+    /// Enable the 8 byte RX and TX FIFOs
+    ///
+    /// The thresholds set the FIFO levels at which the RX FIFO threshold and TX FIFO threshold
+    /// flags are raised, which allows handling several bytes per interrupt at high baud rates.
+    #[cfg(any(
+        // feature = "stm32l4p5",
+        // feature = "stm32l4q5",
+        // feature = "stm32l4r5",
+        // feature = "stm32l4s5",
+        // feature = "stm32l4r7",
+        // feature = "stm32l4s7",
+        feature = "stm32l4r9",
+        feature = "stm32l4s9",
+    ))]
+    pub fn with_fifo(mut self, rx_threshold: FifoThreshold, tx_threshold: FifoThreshold) -> Self {
+        self.fifo = Some((rx_threshold, tx_threshold));
+        self
+    }
 }
 
 impl Default for Config {
@@ -185,12 +351,28 @@ impl Default for Config {
         Config {
             baudrate,
             parity: Parity::ParityNone,
+            word_length: WordLength::DataBits8,
             stopbits: StopBits::STOP1,
             oversampling: Oversampling::Over16,
             character_match: None,
             receiver_timeout: None,
             disable_overrun: false,
             onebit_sampling: false,
+            smartcard: None,
+            de_polarity: DriverEnablePolarity::ActiveHigh,
+            de_assertion_time: 0b1111,
+            de_deassertion_time: 0b1111,
+            #[cfg(any(
+                // feature = "stm32l4p5",
+                // feature = "stm32l4q5",
+                // feature = "stm32l4r5",
+                // feature = "stm32l4s5",
+                // feature = "stm32l4r7",
+                // feature = "stm32l4s7",
+                feature = "stm32l4r9",
+                feature = "stm32l4s9",
+            ))]
+            fifo: None,
         }
     }
 }
@@ -220,12 +402,74 @@ pub struct Tx<USART> {
     _usart: PhantomData<USART>,
 }
 
+/// Interrupt driven serial receiver with an internal ring buffer
+///
+/// Received bytes are moved into the ring buffer by calling `on_interrupt` from the USART
+/// interrupt handler (enable it with `Serial::listen(Event::Rxne)` before splitting). Receive
+/// errors, including overruns, are cleared in the handler so reception continues instead of
+/// stalling until the flag is cleared by hand.
+pub struct BufferedRx<USART, const N: usize> {
+    rx: Rx<USART>,
+    buffer: [u8; N],
+    head: usize,
+    len: usize,
+    overruns: usize,
+}
+
+impl<USART, const N: usize> BufferedRx<USART, N> {
+    fn push(&mut self, byte: u8) {
+        if self.len == N {
+            // Buffer full, the byte is dropped
+            self.overruns = self.overruns.wrapping_add(1);
+            return;
+        }
+
+        self.buffer[(self.head + self.len) % N] = byte;
+        self.len += 1;
+    }
+
+    /// Takes the oldest byte out of the ring buffer, if any
+    pub fn read_buffered(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let byte = self.buffer[self.head];
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+
+        Some(byte)
+    }
+
+    /// Returns the number of bytes waiting in the ring buffer
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no bytes are waiting in the ring buffer
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns how many times data was lost, either due to a hardware overrun or due to a full
+    /// ring buffer
+    pub fn overruns(&self) -> usize {
+        self.overruns
+    }
+
+    /// Releases the underlying receiver, discarding any buffered data
+    pub fn free(self) -> Rx<USART> {
+        self.rx
+    }
+}
+
 macro_rules! hal {
     ($(
         $(#[$meta:meta])*
         $USARTX:ident: (
             $usartX:ident,
             $pclkX:ident,
+            smartcard: $smartcard:literal,
             tx: ($txdma:ident, $dmatxch:path, $dmatxsel:path),
             rx: ($rxdma:ident, $dmarxch:path, $dmarxsel:path)
         ),
@@ -259,6 +503,11 @@ macro_rules! hal {
                 {
                     let config = config.into();
 
+                    // Smartcard mode is only supported by USART1-3 and excludes half-duplex mode
+                    if config.smartcard.is_some() {
+                        assert!($smartcard && !PINS::HALF_DUPLEX);
+                    }
+
                     // enable or reset $USARTX
                     <pac::$USARTX>::enable(apb);
                     <pac::$USARTX>::reset(apb);
@@ -299,10 +548,18 @@ macro_rules! hal {
                     if PINS::FLOWCTL {
                         usart.cr3.modify(|_, w| w.rtse().set_bit().ctse().set_bit());
                     } else if PINS::DEM {
-                        usart.cr3.modify(|_, w| w.dem().set_bit());
-
-                        // Pre/post driver enable set conservative to the max time
-                        usart.cr1.modify(|_, w| w.deat().bits(0b1111).dedt().bits(0b1111));
+                        let active_low = match config.de_polarity {
+                            DriverEnablePolarity::ActiveHigh => false,
+                            DriverEnablePolarity::ActiveLow => true,
+                        };
+                        usart.cr3.modify(|_, w| w.dem().set_bit().dep().bit(active_low));
+
+                        usart.cr1.modify(|_, w| {
+                            w.deat()
+                                .bits(config.de_assertion_time)
+                                .dedt()
+                                .bits(config.de_deassertion_time)
+                        });
                     } else {
                         usart.cr3.modify(|_, w| w.rtse().clear_bit().ctse().clear_bit());
                     }
@@ -322,22 +579,55 @@ macro_rules! hal {
                             w.hdsel().set_bit();
                         }
 
+                        // configure smartcard mode
+                        if let Some(smartcard) = &config.smartcard {
+                            w.scen()
+                                .set_bit()
+                                .nack()
+                                .set_bit()
+                                .scarcnt()
+                                .bits(smartcard.retries);
+                        }
+
                         w
                     });
 
+                    if let Some(smartcard) = &config.smartcard {
+                        usart.gtpr.write(|w| {
+                            w.gt()
+                                .bits(smartcard.guard_time)
+                                .psc()
+                                .bits(smartcard.clock_prescaler)
+                        });
+                    }
+
                     // Configure parity and word length
                     // Unlike most uart devices, the "word length" of this usart device refers to
                     // the size of the data plus the parity bit. I.e. "word length"=8, parity=even
                     // results in 7 bits of data. Therefore, in order to get 8 bits and one parity
                     // bit, we need to set the "word" length to 9 when using parity bits.
-                    let (word_length, parity_control_enable, parity) = match config.parity {
-                        Parity::ParityNone => (false, false, false),
-                        Parity::ParityEven => (true, true, false),
-                        Parity::ParityOdd => (true, true, true),
+                    let (parity_control_enable, parity) = match config.parity {
+                        Parity::ParityNone => (false, false),
+                        Parity::ParityEven => (true, false),
+                        Parity::ParityOdd => (true, true),
+                    };
+                    let data_bits = match config.word_length {
+                        WordLength::DataBits7 => 7,
+                        WordLength::DataBits8 => 8,
+                        WordLength::DataBits9 => 9,
+                    };
+                    // M1:M0 = 0b10 selects 7 bits, 0b00 8 bits and 0b01 9 bits
+                    let (m1, m0) = match data_bits + parity_control_enable as u8 {
+                        7 => (true, false),
+                        8 => (false, false),
+                        9 => (false, true),
+                        // 9 data bits with parity are rejected by `Config`
+                        _ => unreachable!(),
                     };
                     usart.cr1.modify(|_r, w| {
                         w
-                            .m0().bit(word_length)
+                            .m1().bit(m1)
+                            .m0().bit(m0)
                             .ps().bit(parity)
                             .pce().bit(parity_control_enable)
                     });
@@ -361,10 +651,38 @@ macro_rules! hal {
                             w.rtoen().set_bit();
                         }
 
+                        // Output the smartcard clock on CK
+                        if config.smartcard.is_some() {
+                            w.clken().set_bit();
+                        }
+
                         w
                     });
 
 
+                    // Configure the FIFOs, the bits are missing from the SVD
+                    // FIFOEN: CR1 bit 29, TXFTCFG: CR3 bits 31:29, RXFTCFG: CR3 bits 27:25
+                    #[cfg(any(
+                        // feature = "stm32l4p5",
+                        // feature = "stm32l4q5",
+                        // feature = "stm32l4r5",
+                        // feature = "stm32l4s5",
+                        // feature = "stm32l4r7",
+                        // feature = "stm32l4s7",
+                        feature = "stm32l4r9",
+                        feature = "stm32l4s9",
+                    ))]
+                    if let Some((rx_threshold, tx_threshold)) = config.fifo {
+                        usart.cr1.modify(|r, w| unsafe { w.bits(r.bits() | (1 << 29)) });
+                        usart.cr3.modify(|r, w| unsafe {
+                            w.bits(
+                                (r.bits() & !(0b111 << 29 | 0b111 << 25))
+                                    | (tx_threshold as u32) << 29
+                                    | (rx_threshold as u32) << 25,
+                            )
+                        });
+                    }
+
                     // UE: enable USART
                     // RE: enable receiver
                     // TE: enable transceiver
@@ -445,6 +763,54 @@ macro_rules! hal {
                 }
             }
 
+            impl<PINS> Serial<pac::$USARTX, PINS>
+            where
+                PINS: Pins<pac::$USARTX>,
+            {
+                /// Writes `request` and then reads `response.len()` bytes on a single-wire
+                /// half-duplex line
+                ///
+                /// The receiver is disabled while transmitting, so the echo of `request` is not
+                /// received, and re-enabled as soon as the transmission is complete (TC), so the
+                /// first byte of the response is not missed. The receiver is also re-enabled if
+                /// the transmission fails.
+                ///
+                /// Panics if the serial is not configured in half-duplex mode.
+                pub fn write_then_read(
+                    &mut self,
+                    request: &[u8],
+                    response: &mut [u8],
+                ) -> Result<(), Error> {
+                    assert!(PINS::HALF_DUPLEX);
+
+                    let mut tx: Tx<pac::$USARTX> = Tx {
+                        _usart: PhantomData,
+                    };
+                    let mut rx: Rx<pac::$USARTX> = Rx {
+                        _usart: PhantomData,
+                    };
+
+                    self.usart.cr1.modify(|_, w| w.re().clear_bit());
+
+                    let written = request
+                        .iter()
+                        .try_for_each(|byte| nb::block!(tx.write(*byte)))
+                        .and_then(|()| nb::block!(tx.flush()));
+
+                    // Discard anything received before the turnaround
+                    self.usart.rqr.write(|w| w.rxfrq().set_bit());
+                    self.usart.cr1.modify(|_, w| w.re().set_bit());
+
+                    written?;
+
+                    for byte in response {
+                        *byte = nb::block!(serial::Read::read(&mut rx))?;
+                    }
+
+                    Ok(())
+                }
+            }
+
             impl<PINS> serial::Read<u8> for Serial<pac::$USARTX, PINS> {
                 type Error = Error;
 
@@ -571,6 +937,22 @@ macro_rules! hal {
                     }
                 }
 
+                /// Reads a full word, for use with 9 data bits
+                ///
+                /// See [`Config::word_length`].
+                pub fn read_u16(&mut self) -> nb::Result<u16, Error> {
+                    self.check_for_error()?;
+
+                    // NOTE(unsafe) atomic read with no side effects
+                    let usart = unsafe { &*pac::$USARTX::ptr() };
+
+                    if usart.isr.read().rxne().bit_is_set() {
+                        return Ok(usart.rdr.read().rdr().bits());
+                    }
+
+                    Err(nb::Error::WouldBlock)
+                }
+
                 /// Check for, and return, any errors
                 ///
                 /// The `read` methods can only return one error at a time, but
@@ -653,6 +1035,53 @@ macro_rules! hal {
                 }
             }
 
+            impl Rx<pac::$USARTX> {
+                /// Turns the receiver into an interrupt driven receiver with a ring buffer of
+                /// `N` bytes
+                pub fn into_buffered<const N: usize>(self) -> BufferedRx<pac::$USARTX, N> {
+                    BufferedRx {
+                        rx: self,
+                        buffer: [0; N],
+                        head: 0,
+                        len: 0,
+                        overruns: 0,
+                    }
+                }
+            }
+
+            impl<const N: usize> BufferedRx<pac::$USARTX, N> {
+                /// Moves all received bytes into the ring buffer, to be called from the USART
+                /// interrupt handler
+                ///
+                /// Error flags are cleared so the receiver never stalls. A hardware overrun
+                /// increments the overrun counter, the bytes still held in the data register
+                /// are kept.
+                pub fn on_interrupt(&mut self) {
+                    // NOTE(unsafe): Only used for atomic access.
+                    let usart = unsafe { &*pac::$USARTX::ptr() };
+
+                    loop {
+                        let isr = usart.isr.read();
+
+                        if isr.ore().bit_is_set() {
+                            usart.icr.write(|w| w.orecf().clear());
+                            self.overruns = self.overruns.wrapping_add(1);
+                        }
+                        if isr.pe().bit_is_set() || isr.fe().bit_is_set() || isr.nf().bit_is_set() {
+                            usart.icr.write(|w| w.pecf().clear().fecf().clear().ncf().clear());
+                        }
+
+                        if isr.rxne().bit_is_clear() {
+                            break;
+                        }
+
+                        // NOTE(read_volatile) see `Rx::read`
+                        let byte = unsafe { ptr::read_volatile(&usart.rdr as *const _ as *const u8) };
+                        self.push(byte);
+                    }
+                }
+            }
+
             impl crate::dma::CharacterMatch for Rx<pac::$USARTX> {
                 /// Checks to see if the USART peripheral has detected an character match and
                 /// clears the flag
@@ -674,6 +1103,28 @@ macro_rules! hal {
             }
 
             impl Tx<pac::$USARTX> {
+                /// Queues a break character, sent once the current character has been
+                /// transmitted
+                pub fn send_break(&mut self) {
+                    // NOTE(unsafe) atomic write to stateless register
+                    unsafe { (*pac::$USARTX::ptr()).rqr.write(|w| w.sbkrq().set_bit()) };
+                }
+
+                /// Writes a full word, for use with 9 data bits
+                ///
+                /// See [`Config::word_length`].
+                pub fn write_u16(&mut self, word: u16) -> nb::Result<(), Error> {
+                    // NOTE(unsafe) atomic read with no side effects
+                    let usart = unsafe { &*pac::$USARTX::ptr() };
+
+                    if usart.isr.read().txe().bit_is_set() {
+                        usart.tdr.write(|w| w.tdr().bits(word));
+                        Ok(())
+                    } else {
+                        Err(nb::Error::WouldBlock)
+                    }
+                }
+
                 pub fn with_dma(self, channel: $dmatxch) -> $txdma {
                     TxDma {
                         payload: self,
@@ -691,6 +1142,39 @@ macro_rules! hal {
                         channel
                     )
                 }
+
+                /// Starts a circular DMA reception into `buffer` and enables the IDLE line
+                /// interrupt of the USART
+                ///
+                /// Call [`CircBuffer::idle_interrupt`] from the USART interrupt to read out the
+                /// words received since the previous idle line.
+                pub fn circ_read_idle<B>(self, buffer: &'static mut B) -> CircBuffer<B, Self>
+                where
+                    &'static mut B: StaticWriteBuffer<Word = u8>,
+                    B: 'static,
+                {
+                    // NOTE(unsafe) the receiver owns the IDLEIE bit
+                    unsafe { (*pac::$USARTX::ptr()).cr1.modify(|_, w| w.idleie().set_bit()) };
+
+                    self.circ_read(buffer)
+                }
+            }
+
+            impl<B> CircBuffer<B, $rxdma>
+            where
+                B: AsRef<[u8]>,
+            {
+                /// This function should be called from the idle line interrupt of the
+                /// corresponding USART
+                ///
+                /// Clears the IDLE flag and reads the words received since the previous call
+                /// into `buf`. Returns the number of words read.
+                pub fn idle_interrupt(&mut self, buf: &mut [u8]) -> Result<usize, crate::dma::Error> {
+                    // NOTE(unsafe) atomic write to stateless register
+                    unsafe { (*pac::$USARTX::ptr()).icr.write(|w| w.idlecf().set_bit()) };
+
+                    self.read(buf)
+                }
             }
 
             impl $txdma {
@@ -756,6 +1240,113 @@ macro_rules! hal {
                 }
             }
 
+            impl<B> crate::dma::ReadDma<B, u8> for $rxdma
+            where
+                B: StaticWriteBuffer<Word = u8>,
+            {
+                /// Receives into `buffer` using DMA, the buffer is handed back by
+                /// `Transfer::wait` once it has been filled
+                fn read(mut self, mut buffer: B) -> Transfer<W, B, Self> {
+                    // NOTE(unsafe) We own the buffer now and we won't call other `&mut` on it
+                    // until the end of the transfer.
+                    let (ptr, len) = unsafe { buffer.static_write_buffer() };
+                    self.channel.set_peripheral_address(
+                        unsafe { &(*pac::$USARTX::ptr()).rdr as *const _ as u32 },
+                        false,
+                    );
+                    self.channel.set_memory_address(ptr as u32, true);
+                    self.channel.set_transfer_length(len as u16);
+
+                    // Tell DMA to request from serial
+                    self.channel.set_request_line($dmarxsel).unwrap();
+
+                    self.channel.ccr().modify(|_, w| {
+                        w
+                            // memory to memory mode disabled
+                            .mem2mem()
+                            .clear_bit()
+                            // medium channel priority level
+                            .pl()
+                            .medium()
+                            // 8-bit memory size
+                            .msize()
+                            .bits8()
+                            // 8-bit peripheral size
+                            .psize()
+                            .bits8()
+                            // circular mode disabled
+                            .circ()
+                            .clear_bit()
+                            // write to memory
+                            .dir()
+                            .clear_bit()
+                    });
+
+                    // NOTE(compiler_fence) operations on `buffer` should not be reordered after
+                    // the next statement, which starts the DMA transfer
+                    atomic::compiler_fence(Ordering::Release);
+
+                    self.start();
+
+                    Transfer::w(buffer, self)
+                }
+            }
+
+            impl<B> crate::dma::WriteDma<B, u8> for $txdma
+            where
+                B: StaticReadBuffer<Word = u8>,
+            {
+                /// Transmits `buffer` using DMA, the buffer is handed back by `Transfer::wait`
+                /// once it has been sent
+                ///
+                /// The transfer is done as soon as the last byte has been moved to the
+                /// transmitter, use `Tx::flush` to wait until it has left the wire.
+                fn write(mut self, buffer: B) -> Transfer<R, B, Self> {
+                    // NOTE(unsafe) We own the buffer now and we won't call other `&mut` on it
+                    // until the end of the transfer.
+                    let (ptr, len) = unsafe { buffer.static_read_buffer() };
+                    self.channel.set_peripheral_address(
+                        unsafe { &(*pac::$USARTX::ptr()).tdr as *const _ as u32 },
+                        false,
+                    );
+                    self.channel.set_memory_address(ptr as u32, true);
+                    self.channel.set_transfer_length(len as u16);
+
+                    // Tell DMA to request from serial
+                    self.channel.set_request_line($dmatxsel).unwrap();
+
+                    self.channel.ccr().modify(|_, w| {
+                        w
+                            // memory to memory mode disabled
+                            .mem2mem()
+                            .clear_bit()
+                            // medium channel priority level
+                            .pl()
+                            .medium()
+                            // 8-bit memory size
+                            .msize()
+                            .bits8()
+                            // 8-bit peripheral size
+                            .psize()
+                            .bits8()
+                            // circular mode disabled
+                            .circ()
+                            .clear_bit()
+                            // read from memory
+                            .dir()
+                            .set_bit()
+                    });
+
+                    // NOTE(compiler_fence) operations on `buffer` should not be reordered after
+                    // the next statement, which starts the DMA transfer
+                    atomic::compiler_fence(Ordering::Release);
+
+                    self.start();
+
+                    Transfer::r(buffer, self)
+                }
+            }
+
             impl $rxdma {
                 /// Create a frame reader that can either react on the Character match interrupt or
                 /// Transfer Complete from the DMA.
@@ -847,13 +1438,13 @@ macro_rules! hal {
 }
 
 hal! {
-    USART1: (usart1, pclk2, tx: (TxDma1, dma1::C4, DmaInput::Usart1Tx), rx: (RxDma1, dma1::C5, DmaInput::Usart1Rx)),
-    USART2: (usart2, pclk1, tx: (TxDma2, dma1::C7, DmaInput::Usart2Tx), rx: (RxDma2, dma1::C6, DmaInput::Usart2Rx)),
+    USART1: (usart1, pclk2, smartcard: true, tx: (TxDma1, dma1::C4, DmaInput::Usart1Tx), rx: (RxDma1, dma1::C5, DmaInput::Usart1Rx)),
+    USART2: (usart2, pclk1, smartcard: true, tx: (TxDma2, dma1::C7, DmaInput::Usart2Tx), rx: (RxDma2, dma1::C6, DmaInput::Usart2Rx)),
 }
 
 #[cfg(not(any(feature = "stm32l432", feature = "stm32l442")))]
 hal! {
-    USART3: (usart3, pclk1, tx: (TxDma3, dma1::C2, DmaInput::Usart3Tx), rx: (RxDma3, dma1::C3, DmaInput::Usart3Rx)),
+    USART3: (usart3, pclk1, smartcard: true, tx: (TxDma3, dma1::C2, DmaInput::Usart3Tx), rx: (RxDma3, dma1::C3, DmaInput::Usart3Rx)),
 }
 
 #[cfg(any(
@@ -877,7 +1468,7 @@ hal! {
     feature = "stm32l4s9",
 ))]
 hal! {
-    UART4: (uart4, pclk1, tx: (TxDma4, dma2::C3, DmaInput::Uart4Tx), rx: (RxDma4, dma2::C5, DmaInput::Uart4Rx)),
+    UART4: (uart4, pclk1, smartcard: false, tx: (TxDma4, dma2::C3, DmaInput::Uart4Tx), rx: (RxDma4, dma2::C5, DmaInput::Uart4Rx)),
 }
 
 #[cfg(any(
@@ -898,7 +1489,7 @@ hal! {
     feature = "stm32l4s9",
 ))]
 hal! {
-    UART5: (uart5, pclk1, tx: (TxDma5, dma2::C1, DmaInput::Uart5Tx), rx: (RxDma5, dma2::C2, DmaInput::Uart5Rx)),
+    UART5: (uart5, pclk1, smartcard: false, tx: (TxDma5, dma2::C1, DmaInput::Uart5Tx), rx: (RxDma5, dma2::C2, DmaInput::Uart5Rx)),
 }
 
 impl<USART, PINS> fmt::Write for Serial<USART, PINS>
@@ -5,6 +5,7 @@
 //! don't have it (L432xx and L442xx don't, L452xx does). Users of this MCU variant that
 //! don't have it shouldn't attempt to use it. Relevant info is on user-manual level.
 
+use core::marker::PhantomData;
 use core::ptr;
 use core::sync::atomic;
 use core::sync::atomic::Ordering;
@@ -43,6 +44,8 @@ pub trait SckPin<SPI>: private::Sealed {}
 pub trait MisoPin<SPI>: private::Sealed {}
 /// MOSI pin. This trait is sealed and cannot be implemented.
 pub trait MosiPin<SPI>: private::Sealed {}
+/// NSS pin. This trait is sealed and cannot be implemented.
+pub trait NssPin<SPI>: private::Sealed {}
 
 macro_rules! pins {
     ($spi:ident, $af:literal, SCK: [$($sck:ident),*], MISO: [$($miso:ident),*], MOSI: [$($mosi:ident),*]) => {
@@ -61,10 +64,31 @@ macro_rules! pins {
     }
 }
 
+macro_rules! nss_pins {
+    ($spi:ident, $af:literal, NSS: [$($nss:ident),*]) => {
+        $(
+            impl private::Sealed for $nss<Alternate<PushPull, $af>> {}
+            impl NssPin<$spi> for $nss<Alternate<PushPull, $af>> {}
+        )*
+    }
+}
+
+/// Hardware NSS output settings
+pub enum NssMode {
+    /// NSS is driven low as long as the SPI is enabled
+    Output,
+    /// NSS is additionally pulsed high between consecutive frames, which requires the clock
+    /// phase to be `CaptureOnFirstTransition`
+    Pulse,
+}
+
 /// SPI peripheral operating in full duplex master mode
-pub struct Spi<SPI, PINS> {
+///
+/// `WORD` is the frame size, either `u8` or `u16`.
+pub struct Spi<SPI, PINS, WORD = u8> {
     spi: SPI,
     pins: PINS,
+    _word: PhantomData<WORD>,
 }
 
 macro_rules! hal {
@@ -134,7 +158,11 @@ macro_rules! hal {
                             .clear_bit()
                     });
 
-                    Spi { spi, pins }
+                    Spi {
+                        spi,
+                        pins,
+                        _word: PhantomData,
+                    }
                 }
 
                 pub fn $spiX_slave(spi: $SPIX, pins: (SCK, MISO, MOSI), mode: Mode, apb2: &mut <$SPIX as RccBus>::Bus) -> Self
@@ -181,9 +209,56 @@ macro_rules! hal {
                     // SPE: SPI enabled
                     spi.cr1.write(|w| w.spe().set_bit());
 
-                    Spi { spi, pins }
+                    Spi {
+                        spi,
+                        pins,
+                        _word: PhantomData,
+                    }
+                }
+            }
+
+            impl<SCK, MISO, MOSI, WORD> Spi<$SPIX, (SCK, MISO, MOSI), WORD> {
+                /// Lets the SPI drive `nss` as chip select, instead of a manually toggled GPIO
+                ///
+                /// Note that this is only supported in master mode, calling it on an SPI
+                /// configured as slave, or using `NssMode::Pulse` with the clock phase
+                /// `CaptureOnSecondTransition`, will cause a panic.
+                pub fn with_hardware_nss<NSS>(
+                    self,
+                    nss: NSS,
+                    nss_mode: NssMode,
+                ) -> Spi<$SPIX, (SCK, MISO, MOSI, NSS), WORD>
+                where
+                    NSS: NssPin<$SPIX>,
+                {
+                    let cr1 = self.spi.cr1.read();
+                    assert!(cr1.mstr().bit_is_set());
+                    assert!(!matches!(nss_mode, NssMode::Pulse) || cr1.cpha().bit_is_clear());
+
+                    self.spi.cr1.modify(|_, w| w.spe().clear_bit());
+                    // SSOE: Slave Select output enabled
+                    // NSSP: NSS pulse between frames
+                    self.spi.cr2.modify(|_, w| {
+                        w.ssoe()
+                            .set_bit()
+                            .nssp()
+                            .bit(matches!(nss_mode, NssMode::Pulse))
+                    });
+                    // SSM: disable software slave management (NSS pin driven by the SPI)
+                    self.spi
+                        .cr1
+                        .modify(|_, w| w.ssm().clear_bit().spe().set_bit());
+
+                    let (sck, miso, mosi) = self.pins;
+                    Spi {
+                        spi: self.spi,
+                        pins: (sck, miso, mosi, nss),
+                        _word: PhantomData,
+                    }
                 }
+            }
 
+            impl<PINS, WORD> Spi<$SPIX, PINS, WORD> {
                 pub fn clear_overrun(&mut self) {
                     self.spi.dr.read().dr();
                     self.spi.sr.read().ovr();
@@ -214,7 +289,7 @@ macro_rules! hal {
                 }
 
                 /// Releases the SPI peripheral and associated pins
-                pub fn free(self) -> ($SPIX, (SCK, MISO, MOSI)) {
+                pub fn free(self) -> ($SPIX, PINS) {
                     (self.spi, self.pins)
                 }
             }
@@ -264,6 +339,93 @@ macro_rules! hal {
             impl<PINS> crate::hal::blocking::spi::transfer::Default<u8> for Spi<$SPIX, PINS> {}
 
             impl<PINS> crate::hal::blocking::spi::write::Default<u8> for Spi<$SPIX, PINS> {}
+
+            impl<PINS> Spi<$SPIX, PINS> {
+                /// Switches to 16-bit frames
+                #[allow(unused_unsafe)]  // Necessary for stm32l4r9
+                pub fn frame_size_16bit(self) -> Spi<$SPIX, PINS, u16> {
+                    self.spi.cr1.modify(|_, w| w.spe().clear_bit());
+                    // DS: 16-bit data size
+                    // FRXTH: RXNE event is generated if the FIFO level is greater than or equal to
+                    //        16-bit, so that a frame is not read as two separate bytes
+                    self.spi
+                        .cr2
+                        .modify(|_, w| unsafe { w.ds().bits(0b1111).frxth().clear_bit() });
+                    self.spi.cr1.modify(|_, w| w.spe().set_bit());
+
+                    Spi {
+                        spi: self.spi,
+                        pins: self.pins,
+                        _word: PhantomData,
+                    }
+                }
+            }
+
+            impl<PINS> Spi<$SPIX, PINS, u16> {
+                /// Switches back to 8-bit frames
+                #[allow(unused_unsafe)]  // Necessary for stm32l4r9
+                pub fn frame_size_8bit(self) -> Spi<$SPIX, PINS> {
+                    self.spi.cr1.modify(|_, w| w.spe().clear_bit());
+                    // DS: 8-bit data size
+                    // FRXTH: RXNE event is generated if the FIFO level is greater than or equal to
+                    //        8-bit
+                    self.spi
+                        .cr2
+                        .modify(|_, w| unsafe { w.ds().bits(0b111).frxth().set_bit() });
+                    self.spi.cr1.modify(|_, w| w.spe().set_bit());
+
+                    Spi {
+                        spi: self.spi,
+                        pins: self.pins,
+                        _word: PhantomData,
+                    }
+                }
+            }
+
+            impl<PINS> FullDuplex<u16> for Spi<$SPIX, PINS, u16> {
+                type Error = Error;
+
+                fn read(&mut self) -> nb::Result<u16, Error> {
+                    let sr = self.spi.sr.read();
+
+                    Err(if sr.ovr().bit_is_set() {
+                        nb::Error::Other(Error::Overrun)
+                    } else if sr.modf().bit_is_set() {
+                        nb::Error::Other(Error::ModeFault)
+                    } else if sr.crcerr().bit_is_set() {
+                        nb::Error::Other(Error::Crc)
+                    } else if sr.rxne().bit_is_set() {
+                        // NOTE(read_volatile) read a half-word
+                        return Ok(unsafe {
+                            ptr::read_volatile(self.spi.dr.as_ptr() as *const u16)
+                        });
+                    } else {
+                        nb::Error::WouldBlock
+                    })
+                }
+
+                fn send(&mut self, word: u16) -> nb::Result<(), Error> {
+                    let sr = self.spi.sr.read();
+
+                    Err(if sr.ovr().bit_is_set() {
+                        nb::Error::Other(Error::Overrun)
+                    } else if sr.modf().bit_is_set() {
+                        nb::Error::Other(Error::ModeFault)
+                    } else if sr.crcerr().bit_is_set() {
+                        nb::Error::Other(Error::Crc)
+                    } else if sr.txe().bit_is_set() {
+                        // NOTE(write_volatile) write a half-word
+                        unsafe { ptr::write_volatile(self.spi.dr.as_ptr() as *mut u16, word) }
+                        return Ok(());
+                    } else {
+                        nb::Error::WouldBlock
+                    })
+                }
+            }
+
+            impl<PINS> crate::hal::blocking::spi::transfer::Default<u16> for Spi<$SPIX, PINS, u16> {}
+
+            impl<PINS> crate::hal::blocking::spi::write::Default<u16> for Spi<$SPIX, PINS, u16> {}
         )+
     }
 }
@@ -299,6 +461,8 @@ pins!(SPI1, 5,
     MISO: [PA6, PB4, PE14],
     MOSI: [PA7, PB5, PE15]);
 
+nss_pins!(SPI1, 5, NSS: [PA4, PA15, PB0, PE12]);
+
 #[cfg(any(
     // feature = "stm32l471", // missing PAC support for Port G
     feature = "stm32l475",
@@ -318,6 +482,24 @@ pins!(SPI1, 5,
 ))]
 pins!(SPI1, 5, SCK: [PG2], MISO: [PG3], MOSI: [PG4]);
 
+#[cfg(any(
+    // feature = "stm32l471", // missing PAC support for Port G
+    feature = "stm32l475",
+    feature = "stm32l476",
+    feature = "stm32l486",
+    feature = "stm32l496",
+    feature = "stm32l4a6",
+    // feature = "stm32l4p5",
+    // feature = "stm32l4q5",
+    // feature = "stm32l4r5",
+    // feature = "stm32l4s5",
+    // feature = "stm32l4r7",
+    // feature = "stm32l4s7",
+    feature = "stm32l4r9",
+    feature = "stm32l4s9",
+))]
+nss_pins!(SPI1, 5, NSS: [PG5]);
+
 #[cfg(not(any(feature = "stm32l433", feature = "stm32l443",)))]
 use crate::stm32::SPI3;
 
@@ -332,6 +514,9 @@ pins!(SPI3, 6,
     MISO: [PB4, PC11],
     MOSI: [PB5, PC12]);
 
+#[cfg(not(any(feature = "stm32l433", feature = "stm32l443",)))]
+nss_pins!(SPI3, 6, NSS: [PA4, PA15]);
+
 #[cfg(any(
     // feature = "stm32l471", // missing PAC support for Port G
     feature = "stm32l475",
@@ -351,6 +536,24 @@ pins!(SPI3, 6,
 ))]
 pins!(SPI3, 6, SCK: [PG9], MISO: [PG10], MOSI: [PG11]);
 
+#[cfg(any(
+    // feature = "stm32l471", // missing PAC support for Port G
+    feature = "stm32l475",
+    feature = "stm32l476",
+    feature = "stm32l486",
+    feature = "stm32l496",
+    feature = "stm32l4a6",
+    // feature = "stm32l4p5",
+    // feature = "stm32l4q5",
+    // feature = "stm32l4r5",
+    // feature = "stm32l4s5",
+    // feature = "stm32l4r7",
+    // feature = "stm32l4s7",
+    feature = "stm32l4r9",
+    feature = "stm32l4s9",
+))]
+nss_pins!(SPI3, 6, NSS: [PG12]);
+
 use crate::stm32::SPI2;
 
 hal! {
@@ -362,6 +565,8 @@ pins!(SPI2, 5,
     MISO: [PB14, PC2, PD3],
     MOSI: [PB15, PC3, PD4]);
 
+nss_pins!(SPI2, 5, NSS: [PB12, PB9, PD0]);
+
 pub struct SpiPayload<SPI, PINS> {
     spi: Spi<SPI, PINS>,
 }
@@ -79,8 +79,29 @@ pub enum Event {
     TimeOut,
 }
 
+/// Events that can be generated in software
+///
+/// Events which are not implemented by a particular timer, e.g. capture/compare events on the
+/// basic timers TIM6/TIM7, have no effect. The commutation and break events are only available
+/// on TIM15/TIM16/TIM17, see `Timer::trigger_commutation` and `Timer::trigger_break`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventSource {
+    /// Update event (UG), reloads the prescaler and auto-reload shadow registers
+    Update = 1 << 0,
+    /// Capture/compare 1 event (CC1G)
+    CaptureCompare1 = 1 << 1,
+    /// Capture/compare 2 event (CC2G)
+    CaptureCompare2 = 1 << 2,
+    /// Capture/compare 3 event (CC3G)
+    CaptureCompare3 = 1 << 3,
+    /// Capture/compare 4 event (CC4G)
+    CaptureCompare4 = 1 << 4,
+    /// Trigger event (TG)
+    Trigger = 1 << 6,
+}
+
 macro_rules! hal {
-    ($($TIM:ident: ($tim:ident, $frname:ident, $apb:ident, $width:ident),)+) => {
+    ($($TIM:ident: ($tim:ident, $frname:ident, $apb:ident, $timclk:ident, $width:ident),)+) => {
         $(
             impl Periodic for Timer<$TIM> {}
 
@@ -98,7 +119,7 @@ macro_rules! hal {
                     self.tim.cr1.modify(|_, w| w.cen().clear_bit());
 
                     self.timeout = timeout.into();
-                    let ticks = self.clocks.pclk1() / self.timeout; // TODO check pclk that timer is on
+                    let ticks = self.clocks.$timclk() / self.timeout;
                     let psc = u16((ticks - 1) / (1 << 16)).unwrap();
 
                     self.tim.psc.write(|w| unsafe { w.psc().bits(psc) });
@@ -161,9 +182,9 @@ macro_rules! hal {
                     <$TIM>::enable(apb);
                     <$TIM>::reset(apb);
 
-                    let psc = clocks.pclk1() / frequency - 1;
+                    let psc = clocks.$timclk() / frequency - 1;
 
-                    debug_assert!(clocks.pclk1() >= frequency);
+                    debug_assert!(clocks.$timclk() >= frequency);
                     debug_assert!(frequency.raw() > 0);
                     debug_assert!(psc <= core::u16::MAX.into());
 
@@ -240,6 +261,26 @@ macro_rules! hal {
                     self.tim.sr.modify(|_, w| w.uif().clear_bit());
                 }
 
+                /// Generates an `event` in software by writing the EGR register
+                ///
+                /// Note that generating an `EventSource::Update` also sets the update interrupt
+                /// flag.
+                // NOTE(allow) `w.bits()` is only unsafe for some of the timers
+                #[allow(unused_unsafe)]
+                pub fn trigger_event(&mut self, event: EventSource) {
+                    self.tim.egr.write(|w| unsafe { w.bits(event as u32) });
+                }
+
+                /// Pauses the counter, the count is kept so it can be continued with `resume`
+                pub fn pause(&mut self) {
+                    self.tim.cr1.modify(|_, w| w.cen().clear_bit());
+                }
+
+                /// Resumes a counter paused with `pause` from the current count
+                pub fn resume(&mut self) {
+                    self.tim.cr1.modify(|_, w| w.cen().set_bit());
+                }
+
                 /// Get the count of the timer.
                 pub fn count() -> $width {
                     let cnt = unsafe { (*$TIM::ptr()).cnt.read() };
@@ -257,12 +298,39 @@ macro_rules! hal {
     }
 }
 
+macro_rules! break_events {
+    ($($TIM:ident,)+) => {
+        $(
+            impl Timer<$TIM> {
+                /// Generates a capture/compare control update event (COMG) in software
+                // NOTE(allow) `w.bits()` is only unsafe for some of the timers
+                #[allow(unused_unsafe)]
+                pub fn trigger_commutation(&mut self) {
+                    self.tim.egr.write(|w| unsafe { w.bits(1 << 5) });
+                }
+
+                /// Generates a break event (BG) in software
+                // NOTE(allow) `w.bits()` is only unsafe for some of the timers
+                #[allow(unused_unsafe)]
+                pub fn trigger_break(&mut self) {
+                    self.tim.egr.write(|w| unsafe { w.bits(1 << 7) });
+                }
+            }
+        )+
+    }
+}
+
 hal! {
-    TIM2:  (tim2, free_running_tim2, APB1R1, u32),
-    TIM6:  (tim6, free_running_tim6, APB1R1, u16),
-    //TIM7:  (tim7, free_running_tim7, APB1R1, u16),
-    TIM15: (tim15, free_running_tim15, APB2, u16),
-    TIM16: (tim16, free_running_tim16, APB2, u16),
+    TIM2:  (tim2, free_running_tim2, APB1R1, timclk1, u32),
+    TIM6:  (tim6, free_running_tim6, APB1R1, timclk1, u16),
+    //TIM7:  (tim7, free_running_tim7, APB1R1, timclk1, u16),
+    TIM15: (tim15, free_running_tim15, APB2, timclk2, u16),
+    TIM16: (tim16, free_running_tim16, APB2, timclk2, u16),
+}
+
+break_events! {
+    TIM15,
+    TIM16,
 }
 
 // missing PAC support
@@ -289,7 +357,7 @@ hal! {
     feature = "stm32l4s9",
 ))]
 hal! {
-    TIM3:  (tim3, free_running_tim3, tim3en, tim3rst, APB1R1, u32),
+    TIM3:  (tim3, free_running_tim3, tim3en, tim3rst, APB1R1, timclk1, u32),
 }
 */
 
@@ -301,7 +369,7 @@ hal! {
     feature = "stm32l462",
 )))]
 hal! {
-    TIM7:  (tim7, free_running_tim7, APB1R1, u16),
+    TIM7:  (tim7, free_running_tim7, APB1R1, timclk1, u16),
 }
 
 #[cfg(any(
@@ -321,7 +389,27 @@ hal! {
     // feature = "stm32l4s9",
 ))]
 hal! {
-    TIM4:  (tim4, free_running_tim4, APB1R1, u16),
-    TIM5:  (tim5, free_running_tim5, APB1R1, u32),
-    TIM17: (tim17, free_running_tim17, APB2, u16),
+    TIM4:  (tim4, free_running_tim4, APB1R1, timclk1, u16),
+    TIM5:  (tim5, free_running_tim5, APB1R1, timclk1, u32),
+    TIM17: (tim17, free_running_tim17, APB2, timclk2, u16),
+}
+
+#[cfg(any(
+    feature = "stm32l475",
+    feature = "stm32l476",
+    feature = "stm32l485",
+    feature = "stm32l486",
+    feature = "stm32l496",
+    feature = "stm32l4a6",
+    // feature = "stm32l4p5",
+    // feature = "stm32l4q5",
+    // feature = "stm32l4r5",
+    // feature = "stm32l4s5",
+    // feature = "stm32l4r7",
+    // feature = "stm32l4s7",
+    // feature = "stm32l4r9",
+    // feature = "stm32l4s9",
+))]
+break_events! {
+    TIM17,
 }